@@ -38,6 +38,7 @@ use {
 };
 
 pub mod byte_utils;
+pub mod payload;
 pub mod state;
 
 use crate::byte_utils::{
@@ -286,18 +287,15 @@ impl TokenBridge {
 
         env::log_str(&hex::encode(&vaa.payload));
 
-        let data: &[u8] = &vaa.payload[1..];
-
-        let amount = data.get_u256(0);
-        let token_address = data.get_bytes32(32).to_vec();
-        let token_chain = data.get_u16(64);
-        let recipient = data.get_bytes32(66).to_vec();
-        let recipient_chain = data.get_u16(98);
-        let fee: (u128, u128) = if action == 1 {
-            data.get_u256(100)
-        } else {
-            (0, 0)
-        };
+        let payload::TransferInfo {
+            amount,
+            token_address,
+            token_chain,
+            recipient,
+            recipient_chain,
+            fee,
+            ..
+        } = payload::TransferInfo::parse(&vaa.payload);
 
         if recipient_chain != CHAIN_ID_NEAR {
             env::panic_str("InvalidRecipientChain");
@@ -496,12 +494,13 @@ impl TokenBridge {
         env::log_str(&hex::encode(&vaa.payload));
 
         let data: &[u8] = &vaa.payload[1..];
+        let asset_meta = payload::AssetMeta::parse(&vaa.payload);
 
-        let token_chain = data.get_u16(32);
+        let token_chain = asset_meta.token_chain;
         if token_chain == CHAIN_ID_NEAR {
             env::panic_str("CannotAttestNearAssets");
         }
-        let tkey = self.token_key(data[0..32].to_vec(), token_chain);
+        let tkey = self.token_key(asset_meta.token_address.clone(), token_chain);
 
         env::log_str(&format!(
             "token-bridge/{}#{}: vaa_asset_meta: {} ",
@@ -514,7 +513,7 @@ impl TokenBridge {
 
         let asset_token_account;
 
-        let mut decimals = data.get_u8(34);
+        let mut decimals = asset_meta.decimals;
 
         if self.key_map.contains_key(&tkey) {
             asset_token_account = self.key_map.get(&tkey).unwrap();
@@ -534,7 +533,7 @@ impl TokenBridge {
             let d = TokenData {
                 meta: data.to_vec(),
                 decimals,
-                address: hex::encode(&data[0..32]),
+                address: hex::encode(&asset_meta.token_address),
                 chain: token_chain,
             };
 
@@ -561,9 +560,7 @@ impl TokenBridge {
             deposit -= required_cost;
         }
 
-        let symbol = data.get_bytes32(35).to_vec();
-        let name = data.get_bytes32(67).to_vec();
-        let wname = get_string_from_32(&name);
+        let wname = get_string_from_32(&asset_meta.name);
 
         // Decimals are capped at 8 in wormhole
         if decimals > 8 {
@@ -573,7 +570,7 @@ impl TokenBridge {
         let ft = FungibleTokenMetadata {
             spec: FT_METADATA_SPEC.to_string(),
             name: wname,
-            symbol: get_string_from_32(&symbol),
+            symbol: get_string_from_32(&asset_meta.symbol),
             icon: None,
             reference: None,
             reference_hash: None,
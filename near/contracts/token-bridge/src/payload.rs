@@ -0,0 +1,145 @@
+use crate::byte_utils::ByteUtils;
+
+// Token bridge payloads
+
+/// Token bridge transfer, decoded from the payload of a transfer (1) or a transfer with payload (3)
+/// VAA. Transfers with payload carry no relayer fee, the sender and payload are only set for them.
+pub struct TransferInfo {
+    pub amount:          (u128, u128),
+    pub token_address:   Vec<u8>,
+    pub token_chain:     u16,
+    pub recipient:       Vec<u8>,
+    pub recipient_chain: u16,
+    pub fee:             (u128, u128),
+    pub sender:          Vec<u8>,
+    pub payload:         Vec<u8>,
+}
+
+impl TransferInfo {
+    /* Payload format, after the action byte:
+
+    0   uint256     amount
+    32  [32]uint8   token_address
+    64  uint16      token_chain
+    66  [32]uint8   recipient
+    98  uint16      recipient_chain
+    100 uint256     fee (action 1) or [32]uint8 sender (action 3)
+    132 []uint8     payload (action 3)
+    */
+
+    pub fn parse(payload: &[u8]) -> Self {
+        let action = payload.get_u8(0);
+        let data: &[u8] = &payload[1..];
+
+        let (fee, sender, payload) = if action == 1 {
+            (data.get_u256(100), vec![], vec![])
+        } else {
+            ((0, 0), data.get_bytes32(100).to_vec(), data[132..].to_vec())
+        };
+
+        TransferInfo {
+            amount: data.get_u256(0),
+            token_address: data.get_bytes32(32).to_vec(),
+            token_chain: data.get_u16(64),
+            recipient: data.get_bytes32(66).to_vec(),
+            recipient_chain: data.get_u16(98),
+            fee,
+            sender,
+            payload,
+        }
+    }
+}
+
+/// Token metadata, decoded from the payload of an asset meta (2) VAA.
+pub struct AssetMeta {
+    pub token_address: Vec<u8>,
+    pub token_chain:   u16,
+    pub decimals:      u8,
+    pub symbol:        Vec<u8>,
+    pub name:          Vec<u8>,
+}
+
+impl AssetMeta {
+    /* Payload format, after the action byte:
+
+    0   [32]uint8   token_address
+    32  uint16      token_chain
+    34  uint8       decimals
+    35  [32]uint8   symbol
+    67  [32]uint8   name
+    */
+
+    pub fn parse(payload: &[u8]) -> Self {
+        let data: &[u8] = &payload[1..];
+
+        AssetMeta {
+            token_address: data.get_bytes32(0).to_vec(),
+            token_chain:   data.get_u16(32),
+            decimals:      data.get_u8(34),
+            symbol:        data.get_bytes32(35).to_vec(),
+            name:          data.get_bytes32(67).to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::byte_utils::get_string_from_32,
+        wormhole_conformance::{
+            Names,
+            TokenBridgePayload,
+        },
+    };
+
+    fn u256((high, low): (u128, u128)) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out[..16].copy_from_slice(&high.to_be_bytes());
+        out[16..].copy_from_slice(&low.to_be_bytes());
+        out
+    }
+
+    /// Decode every case in the shared conformance corpus, names are decoded the way the contract
+    /// decodes them when creating wrapped assets.
+    #[test]
+    fn conformance_corpus() {
+        wormhole_conformance::token_bridge(Names::Lossy, |payload| match payload[0] {
+            1 => {
+                let t = TransferInfo::parse(payload);
+                TokenBridgePayload::Transfer {
+                    amount:        u256(t.amount),
+                    token_address: t.token_address,
+                    token_chain:   t.token_chain,
+                    to:            t.recipient,
+                    to_chain:      t.recipient_chain,
+                    fee:           u256(t.fee),
+                }
+            }
+            3 => {
+                let t = TransferInfo::parse(payload);
+                assert_eq!(t.fee, (0, 0));
+                TokenBridgePayload::TransferWithPayload {
+                    amount:        u256(t.amount),
+                    token_address: t.token_address,
+                    token_chain:   t.token_chain,
+                    to:            t.recipient,
+                    to_chain:      t.recipient_chain,
+                    from_address:  t.sender,
+                    payload:       t.payload,
+                }
+            }
+            2 => {
+                let meta = AssetMeta::parse(payload);
+                TokenBridgePayload::AssetMeta {
+                    token_address: meta.token_address,
+                    token_chain:   meta.token_chain,
+                    decimals:      meta.decimals,
+                    symbol:        get_string_from_32(&meta.symbol),
+                    name:          get_string_from_32(&meta.name),
+                }
+            }
+            other => panic!("unexpected action {}", other),
+        });
+    }
+}
@@ -5,7 +5,10 @@ import {
 } from "@solana/web3.js";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { createReadOnlyNftBridgeProgramInterface } from "../program";
-import { getPostMessageAccounts } from "../../wormhole";
+import {
+  getPostMessageAccounts,
+  getFeeScheduleAccountMeta,
+} from "../../wormhole";
 import {
   deriveAuthoritySignerKey,
   deriveNftBridgeConfigKey,
@@ -53,7 +56,7 @@ export function createTransferWrappedInstruction(
       tokenId
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: [getFeeScheduleAccountMeta(wormholeProgramId)],
     preInstructions: undefined,
     postInstructions: undefined,
   });
@@ -4,7 +4,10 @@ import {
   TransactionInstruction,
 } from "@solana/web3.js";
 import { createReadOnlyTokenBridgeProgramInterface } from "../program";
-import { getPostMessageAccounts } from "../../wormhole";
+import {
+  getPostMessageAccounts,
+  getFeeScheduleAccountMeta,
+} from "../../wormhole";
 import {
   deriveSplTokenMetadataKey,
   deriveTokenBridgeConfigKey,
@@ -34,7 +37,7 @@ export function createAttestTokenInstruction(
       message
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: [getFeeScheduleAccountMeta(wormholeProgramId)],
     preInstructions: undefined,
     postInstructions: undefined,
   });
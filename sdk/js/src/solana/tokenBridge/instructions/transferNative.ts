@@ -5,7 +5,10 @@ import {
 } from "@solana/web3.js";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { createReadOnlyTokenBridgeProgramInterface } from "../program";
-import { getPostMessageCpiAccounts } from "../../wormhole";
+import {
+  getPostMessageCpiAccounts,
+  getFeeScheduleAccountMeta,
+} from "../../wormhole";
 import {
   deriveAuthoritySignerKey,
   deriveCustodySignerKey,
@@ -47,7 +50,7 @@ export function createTransferNativeInstruction(
       mint
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: [getFeeScheduleAccountMeta(wormholeProgramId)],
     preInstructions: undefined,
     postInstructions: undefined,
   });
@@ -0,0 +1,8 @@
+import { PublicKey, PublicKeyInitData } from "@solana/web3.js";
+import { deriveAddress } from "../../utils";
+
+export function deriveFeeScheduleKey(
+  wormholeProgramId: PublicKeyInitData
+): PublicKey {
+  return deriveAddress([Buffer.from("FeeSchedule")], wormholeProgramId);
+}
@@ -2,6 +2,7 @@ export * from "./claim";
 export * from "./config";
 export * from "./emitter";
 export * from "./feeCollector";
+export * from "./feeSchedule";
 export * from "./guardianSet";
 export * from "./postedVaa";
 export * from "./sequence";
@@ -28,6 +28,16 @@ export function derivePostedVaaKey(
   return deriveAddress([Buffer.from("PostedVAA"), hash], wormholeProgramId);
 }
 
+export function derivePostedVaaPayerKey(
+  wormholeProgramId: PublicKeyInitData,
+  postedVaa: PublicKeyInitData
+): PublicKey {
+  return deriveAddress(
+    [Buffer.from("PostedVAAPayer"), new PublicKey(postedVaa).toBuffer()],
+    wormholeProgramId
+  );
+}
+
 export async function getPostedVaa(
   connection: Connection,
   wormholeProgramId: PublicKeyInitData,
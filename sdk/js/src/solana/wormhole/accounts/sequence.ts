@@ -32,16 +32,21 @@ export async function getSequenceTracker(
 
 export class SequenceTracker {
   sequence: bigint;
+  feeScheduleRequired: boolean;
 
-  constructor(sequence: bigint) {
+  constructor(sequence: bigint, feeScheduleRequired: boolean = false) {
     this.sequence = sequence;
+    this.feeScheduleRequired = feeScheduleRequired;
   }
 
   static deserialize(data: Buffer): SequenceTracker {
-    if (data.length != 8) {
-      throw new Error("data.length != 8");
+    if (data.length != 8 && data.length != 9) {
+      throw new Error("data.length != 8 && data.length != 9");
     }
-    return new SequenceTracker(data.readBigUInt64LE(0));
+    return new SequenceTracker(
+      data.readBigUInt64LE(0),
+      data.length == 9 && data[8] != 0
+    );
   }
 
   value(): bigint {
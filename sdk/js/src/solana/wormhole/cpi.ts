@@ -2,6 +2,7 @@ import { PublicKey, PublicKeyInitData } from "@solana/web3.js";
 import {
   deriveEmitterSequenceKey,
   deriveFeeCollectorKey,
+  deriveFeeScheduleKey,
   deriveWormholeEmitterKey,
   deriveWormholeBridgeDataKey,
   getEmitterKeys,
@@ -25,6 +26,10 @@ export interface WormholeDerivedAccounts {
    * seeds = ["fee_collector"], seeds::program = wormholeProgram
    */
   wormholeFeeCollector: PublicKey;
+  /**
+   * seeds = ["FeeSchedule"], seeds::program = wormholeProgram
+   */
+  wormholeFeeSchedule: PublicKey;
 }
 
 /**
@@ -45,6 +50,7 @@ export function getWormholeDerivedAccounts(
     wormholeEmitter,
     wormholeSequence,
     wormholeFeeCollector: deriveFeeCollectorKey(wormholeProgramId),
+    wormholeFeeSchedule: deriveFeeScheduleKey(wormholeProgramId),
   };
 }
 
@@ -85,6 +91,7 @@ export function getPostMessageCpiAccounts(
     wormholeEmitter: accounts.emitter,
     wormholeSequence: accounts.sequence,
     wormholeFeeCollector: accounts.feeCollector,
+    wormholeFeeSchedule: accounts.feeSchedule,
     clock: accounts.clock,
     rent: accounts.rent,
     systemProgram: accounts.systemProgram,
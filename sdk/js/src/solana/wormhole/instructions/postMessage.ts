@@ -1,4 +1,5 @@
 import {
+  AccountMeta,
   PublicKey,
   PublicKeyInitData,
   SYSVAR_CLOCK_PUBKEY,
@@ -8,6 +9,7 @@ import {
 import {
   deriveWormholeBridgeDataKey,
   deriveFeeCollectorKey,
+  deriveFeeScheduleKey,
   getEmitterKeys,
 } from "../accounts";
 
@@ -22,6 +24,8 @@ export interface PostMessageAccounts {
   clock: PublicKey;
   rent: PublicKey;
   systemProgram: PublicKey;
+  /** Passed after the other accounts, the Core Bridge looks it up by address */
+  feeSchedule: PublicKey;
 }
 
 export function getPostMessageAccounts(
@@ -44,5 +48,23 @@ export function getPostMessageAccounts(
     clock: SYSVAR_CLOCK_PUBKEY,
     rent: SYSVAR_RENT_PUBKEY,
     systemProgram: SystemProgram.programId,
+    feeSchedule: deriveFeeScheduleKey(wormholeProgramId),
+  };
+}
+
+/**
+ * Programs posting messages through a cross-program invocation forward the
+ * Core Bridge fee schedule, append this to the accounts of their instructions.
+ *
+ * @param wormholeProgramId
+ * @returns
+ */
+export function getFeeScheduleAccountMeta(
+  wormholeProgramId: PublicKeyInitData
+): AccountMeta {
+  return {
+    pubkey: deriveFeeScheduleKey(wormholeProgramId),
+    isSigner: false,
+    isWritable: false,
   };
 }
@@ -1,4 +1,5 @@
 import {
+  AccountMeta,
   PublicKey,
   PublicKeyInitData,
   TransactionInstruction,
@@ -11,6 +12,7 @@ import {
   deriveWormholeBridgeDataKey,
   deriveGuardianSetKey,
   derivePostedVaaKey,
+  derivePostedVaaPayerKey,
 } from "../accounts";
 import { isBytes, ParsedVaa, parseVaa, SignedVaa } from "../../../vaa";
 
@@ -58,7 +60,9 @@ export function createPostVaaInstruction(
       parsed
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: [
+      getPostedVaaPayerAccountMeta(wormholeProgramId, parsed),
+    ],
     preInstructions: undefined,
     postInstructions: undefined,
   });
@@ -96,3 +100,22 @@ export function getPostVaaAccounts(
     systemProgram: SystemProgram.programId,
   };
 }
+
+/**
+ * The account recording `payer` as the payer of the posted VAA, so that only
+ * they can close it and reclaim its rent.
+ */
+export function getPostedVaaPayerAccountMeta(
+  wormholeProgramId: PublicKeyInitData,
+  vaa: SignedVaa | ParsedVaa
+): AccountMeta {
+  const parsed = isBytes(vaa) ? parseVaa(vaa) : vaa;
+  return {
+    pubkey: derivePostedVaaPayerKey(
+      wormholeProgramId,
+      derivePostedVaaKey(wormholeProgramId, parsed.hash)
+    ),
+    isSigner: false,
+    isWritable: true,
+  };
+}
@@ -0,0 +1,336 @@
+//! Wormhole identifies every address, on every chain, as 32 bytes. Chains with shorter addresses,
+//! such as EVM chains, left-pad them with zeros, while chains with other address schemes hash them
+//! down to 32 bytes. This module converts between these 32 byte addresses and the native address
+//! formats used by each chain.
+use core::fmt;
+use core::str::FromStr;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use bech32::{FromBase32, ToBase32, Variant};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    require, Chain, ForeignAddress,
+    WormholeError::{self, InvalidAddress},
+};
+
+/// A 32 byte address as found in VAA's and payloads. The bytes are kept exactly as they appear on
+/// the wire; conversion to a chain's native format is done with `to_native` or one of the format
+/// specific methods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniversalAddress(
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))] pub [u8; 32],
+);
+
+impl UniversalAddress {
+    pub fn new(bytes: [u8; 32]) -> Self {
+        UniversalAddress(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Left-pad an address of up to 32 bytes, this fails if the address is longer than 32 bytes.
+    pub fn from_slice(address: &[u8]) -> Result<Self, WormholeError> {
+        require!(address.len() <= 32, InvalidAddress);
+        let mut bytes = [0u8; 32];
+        bytes[32 - address.len()..].copy_from_slice(address);
+        Ok(UniversalAddress(bytes))
+    }
+
+    /// Return the last `N` bytes of the address, this fails if any of the padding bytes before them
+    /// are not zero.
+    pub fn to_short<const N: usize>(&self) -> Result<[u8; N], WormholeError> {
+        require!(N <= 32, InvalidAddress);
+        let (padding, address) = self.0.split_at(32 - N);
+        require!(padding.iter().all(|&b| b == 0), InvalidAddress);
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(address);
+        Ok(bytes)
+    }
+
+    /// Parse a 20 byte EVM address in hex, with or without the `0x` prefix. Checksums are not
+    /// verified.
+    pub fn from_evm(address: &str) -> Result<Self, WormholeError> {
+        let address = address.strip_prefix("0x").unwrap_or(address);
+        let bytes = hex::decode(address).map_err(|_| InvalidAddress)?;
+        require!(bytes.len() == 20, InvalidAddress);
+        Self::from_slice(&bytes)
+    }
+
+    /// Render as a `0x` prefixed, lowercase 20 byte EVM address.
+    pub fn to_evm(&self) -> Result<String, WormholeError> {
+        Ok(format!("0x{}", hex::encode(self.to_short::<20>()?)))
+    }
+
+    /// Parse a base58 encoded Solana address.
+    pub fn from_base58(address: &str) -> Result<Self, WormholeError> {
+        let bytes = bs58::decode(address)
+            .into_vec()
+            .map_err(|_| InvalidAddress)?;
+        require!(bytes.len() == 32, InvalidAddress);
+        Self::from_slice(&bytes)
+    }
+
+    /// Render as a base58 encoded Solana address.
+    pub fn to_base58(&self) -> String {
+        bs58::encode(&self.0).into_string()
+    }
+
+    /// Parse a bech32 address as used by Cosmos chains, returning the human readable prefix along
+    /// with the address. Both 20 byte account addresses and 32 byte contract addresses are
+    /// accepted, 20 byte addresses are left-padded.
+    pub fn from_bech32(address: &str) -> Result<(String, Self), WormholeError> {
+        let (prefix, data, variant) = bech32::decode(address).map_err(|_| InvalidAddress)?;
+        require!(variant == Variant::Bech32, InvalidAddress);
+        let bytes = Vec::<u8>::from_base32(&data).map_err(|_| InvalidAddress)?;
+        require!(bytes.len() == 20 || bytes.len() == 32, InvalidAddress);
+        Ok((prefix, Self::from_slice(&bytes)?))
+    }
+
+    /// Render as a bech32 address with the given human readable prefix. Addresses that are 20
+    /// bytes once padding is removed are rendered as account addresses, anything else is rendered
+    /// as a 32 byte contract address.
+    pub fn to_bech32(&self, prefix: &str) -> Result<String, WormholeError> {
+        let bytes = match self.to_short::<20>() {
+            Ok(short) => short.to_vec(),
+            Err(_) => self.0.to_vec(),
+        };
+        bech32::encode(prefix, bytes.to_base32(), Variant::Bech32).map_err(|_| InvalidAddress)
+    }
+
+    /// Emitters on NEAR are identified by the SHA-256 hash of their account ID. The hash cannot be
+    /// reversed, so mapping back to an account ID requires a lookup such as the one kept by the
+    /// NEAR token bridge.
+    pub fn from_near_account(account: &str) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&Sha256::digest(account.as_bytes()));
+        UniversalAddress(bytes)
+    }
+
+    /// Parse an address in the native format of `chain`. EVM chains use hex, Solana uses base58,
+    /// Cosmos chains use bech32 with the chain's prefix and NEAR account IDs are hashed. All other
+    /// chains use 32 byte hex, with or without the `0x` prefix.
+    pub fn from_native(chain: Chain, address: &str) -> Result<Self, WormholeError> {
+        if is_evm(chain) {
+            return Self::from_evm(address);
+        }
+
+        if let Some(expected) = bech32_prefix(chain) {
+            let (prefix, address) = Self::from_bech32(address)?;
+            require!(prefix == expected, InvalidAddress);
+            return Ok(address);
+        }
+
+        match chain {
+            Chain::Solana | Chain::Pythnet => Self::from_base58(address),
+            Chain::Near => Ok(Self::from_near_account(address)),
+            _ => address.parse(),
+        }
+    }
+
+    /// Render the address in the native format of `chain`, the inverse of `from_native`. NEAR
+    /// addresses are hashes and are rendered as hex.
+    pub fn to_native(&self, chain: Chain) -> Result<String, WormholeError> {
+        if is_evm(chain) {
+            return self.to_evm();
+        }
+
+        if let Some(prefix) = bech32_prefix(chain) {
+            return self.to_bech32(prefix);
+        }
+
+        match chain {
+            Chain::Solana | Chain::Pythnet => Ok(self.to_base58()),
+            _ => Ok(self.to_string()),
+        }
+    }
+}
+
+/// Chains that use 20 byte Ethereum style addresses.
+fn is_evm(chain: Chain) -> bool {
+    matches!(
+        chain,
+        Chain::Ethereum
+            | Chain::Binance
+            | Chain::Polygon
+            | Chain::AVAX
+            | Chain::Oasis
+            | Chain::Aurora
+            | Chain::Fantom
+            | Chain::Karura
+            | Chain::Acala
+            | Chain::Klaytn
+            | Chain::Celo
+            | Chain::Moonbeam
+            | Chain::Neon
+            | Chain::Arbitrum
+            | Chain::Optimism
+            | Chain::Gnosis
+    )
+}
+
+/// Human readable prefix of bech32 addresses on Cosmos chains.
+fn bech32_prefix(chain: Chain) -> Option<&'static str> {
+    match chain {
+        Chain::Terra | Chain::Terra2 => Some("terra"),
+        Chain::Injective => Some("inj"),
+        Chain::Osmosis => Some("osmo"),
+        Chain::Xpla => Some("xpla"),
+        Chain::Wormchain => Some("wormhole"),
+        _ => None,
+    }
+}
+
+impl From<ForeignAddress> for UniversalAddress {
+    fn from(bytes: ForeignAddress) -> Self {
+        UniversalAddress(bytes)
+    }
+}
+
+impl From<UniversalAddress> for ForeignAddress {
+    fn from(address: UniversalAddress) -> Self {
+        address.0
+    }
+}
+
+/// Addresses are rendered as `0x` prefixed 32 byte hex.
+impl fmt::Display for UniversalAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+/// Parses 32 byte hex, with or without the `0x` prefix.
+impl FromStr for UniversalAddress {
+    type Err = WormholeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(s, &mut bytes).map_err(|_| InvalidAddress)?;
+        Ok(UniversalAddress(bytes))
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::UniversalAddress;
+    use crate::Chain;
+
+    #[test]
+    fn test_evm() {
+        let address =
+            UniversalAddress::from_evm("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2").unwrap();
+        assert_eq!(
+            address.to_string(),
+            "0x000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+        );
+        assert_eq!(
+            address.to_native(Chain::Ethereum).unwrap(),
+            "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+        );
+        assert_eq!(
+            UniversalAddress::from_native(
+                Chain::Polygon,
+                "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+            )
+            .unwrap(),
+            address
+        );
+        let gnosis = address.to_native(Chain::Gnosis).unwrap();
+        assert_eq!(gnosis, "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
+        assert_eq!(
+            UniversalAddress::from_native(Chain::Gnosis, &gnosis).unwrap(),
+            address
+        );
+
+        // Addresses with non-zero padding are not EVM addresses.
+        let mut bytes = *address.as_bytes();
+        bytes[11] = 1;
+        assert!(UniversalAddress::new(bytes).to_evm().is_err());
+        assert!(UniversalAddress::new(bytes)
+            .to_native(Chain::Gnosis)
+            .is_err());
+        assert!(UniversalAddress::from_evm("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756c").is_err());
+    }
+
+    #[test]
+    fn test_solana() {
+        // The devnet token bridge.
+        let native = "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE";
+        let address = UniversalAddress::from_native(Chain::Solana, native).unwrap();
+        assert_eq!(
+            address.to_string(),
+            "0x95f83a27e90c622a98c037353f271fd8f5f57b4dc18ebf5ff75a934724bd0491"
+        );
+        assert_eq!(address.to_native(Chain::Solana).unwrap(), native);
+        assert!(
+            UniversalAddress::from_base58("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5")
+                .unwrap()
+                .to_evm()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_bech32() {
+        // 20 byte account addresses are padded, 32 byte contract addresses are not.
+        let account = "terra1x46rqay4d3cssq8gxxvqz8xt6nwlz4td20k38v";
+        let address = UniversalAddress::from_native(Chain::Terra, account).unwrap();
+        assert_eq!(address.to_short::<20>().unwrap().len(), 20);
+        assert_eq!(address.to_native(Chain::Terra).unwrap(), account);
+
+        let contract = "terra14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9ssrc8au";
+        let address = UniversalAddress::from_native(Chain::Terra2, contract).unwrap();
+        assert!(address.to_short::<20>().is_err());
+        assert_eq!(address.to_native(Chain::Terra2).unwrap(), contract);
+
+        // The prefix must match the chain but is otherwise configurable.
+        assert!(UniversalAddress::from_native(Chain::Injective, contract).is_err());
+        let (prefix, _) = UniversalAddress::from_bech32(contract).unwrap();
+        assert_eq!(prefix, "terra");
+        assert!(address.to_bech32("xpla").unwrap().starts_with("xpla1"));
+
+        // Other Cosmos chains round-trip through their own prefix.
+        let osmosis = address.to_native(Chain::Osmosis).unwrap();
+        assert!(osmosis.starts_with("osmo1"));
+        assert_eq!(
+            UniversalAddress::from_native(Chain::Osmosis, &osmosis).unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn test_near() {
+        let address =
+            UniversalAddress::from_native(Chain::Near, "contract.portalbridge.near").unwrap();
+        assert_eq!(
+            address,
+            UniversalAddress::from_near_account("contract.portalbridge.near")
+        );
+        assert_eq!(
+            address
+                .to_native(Chain::Near)
+                .unwrap()
+                .parse::<UniversalAddress>()
+                .unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn test_padding() {
+        let address = UniversalAddress::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(address.to_short::<3>().unwrap(), [1, 2, 3]);
+        assert_eq!(address.to_short::<20>().unwrap()[17..], [1, 2, 3]);
+        assert!(address.to_short::<2>().is_err());
+        assert!(UniversalAddress::from_slice(&[0u8; 33]).is_err());
+        assert!("0x1234".parse::<UniversalAddress>().is_err());
+    }
+}
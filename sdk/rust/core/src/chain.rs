@@ -1,41 +1,236 @@
 //! Exposes an API implementation depending on which feature flags have been toggled for the
 //! library. Check submodules for chain runtime specific documentation.
-use std::convert::TryFrom; // Remove in 2021
+use core::fmt;
+use core::str::FromStr;
+
+use crate::WormholeError;
 
 /// Chain contains a mapping of Wormhole supported chains to their u16 representation. These are
 /// universally defined among all Wormhole contracts.
-#[repr(u16)]
-#[derive(Clone, Debug, PartialEq, Eq)]
+///
+/// New chains are onboarded regularly, so any ID that is not yet known to this library is kept as
+/// `Unknown` rather than being rejected. Known IDs always convert to their named variant, so
+/// `Unknown` never holds an ID that has a name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Chain {
-    All = 0,
-    Solana = 1,
-    Ethereum = 2,
-    Terra = 3,
-    Binance = 4,
-    Polygon = 5,
-    AVAX = 6,
-    Oasis = 7,
+    All,
+    Solana,
+    Ethereum,
+    Terra,
+    Binance,
+    Polygon,
+    AVAX,
+    Oasis,
+    Algorand,
+    Aurora,
+    Fantom,
+    Karura,
+    Acala,
+    Klaytn,
+    Celo,
+    Near,
+    Moonbeam,
+    Neon,
+    Terra2,
+    Injective,
+    Osmosis,
+    Sui,
+    Aptos,
+    Arbitrum,
+    Optimism,
+    Gnosis,
+    Pythnet,
+    Xpla,
+    Wormchain,
+    Unknown(u16),
 }
 
-impl TryFrom<u16> for Chain {
-    type Error = ();
-    fn try_from(other: u16) -> Result<Chain, Self::Error> {
+impl Default for Chain {
+    fn default() -> Self {
+        Chain::All
+    }
+}
+
+impl From<u16> for Chain {
+    fn from(other: u16) -> Chain {
         match other {
-            0 => Ok(Chain::All),
-            1 => Ok(Chain::Solana),
-            2 => Ok(Chain::Ethereum),
-            3 => Ok(Chain::Terra),
-            4 => Ok(Chain::Binance),
-            5 => Ok(Chain::Polygon),
-            6 => Ok(Chain::AVAX),
-            7 => Ok(Chain::Oasis),
-            _ => Err(()),
+            0 => Chain::All,
+            1 => Chain::Solana,
+            2 => Chain::Ethereum,
+            3 => Chain::Terra,
+            4 => Chain::Binance,
+            5 => Chain::Polygon,
+            6 => Chain::AVAX,
+            7 => Chain::Oasis,
+            8 => Chain::Algorand,
+            9 => Chain::Aurora,
+            10 => Chain::Fantom,
+            11 => Chain::Karura,
+            12 => Chain::Acala,
+            13 => Chain::Klaytn,
+            14 => Chain::Celo,
+            15 => Chain::Near,
+            16 => Chain::Moonbeam,
+            17 => Chain::Neon,
+            18 => Chain::Terra2,
+            19 => Chain::Injective,
+            20 => Chain::Osmosis,
+            21 => Chain::Sui,
+            22 => Chain::Aptos,
+            23 => Chain::Arbitrum,
+            24 => Chain::Optimism,
+            25 => Chain::Gnosis,
+            26 => Chain::Pythnet,
+            28 => Chain::Xpla,
+            3104 => Chain::Wormchain,
+            c => Chain::Unknown(c),
         }
     }
 }
 
-impl Default for Chain {
-    fn default() -> Self {
-        Self::All
+impl From<Chain> for u16 {
+    fn from(other: Chain) -> u16 {
+        match other {
+            Chain::All => 0,
+            Chain::Solana => 1,
+            Chain::Ethereum => 2,
+            Chain::Terra => 3,
+            Chain::Binance => 4,
+            Chain::Polygon => 5,
+            Chain::AVAX => 6,
+            Chain::Oasis => 7,
+            Chain::Algorand => 8,
+            Chain::Aurora => 9,
+            Chain::Fantom => 10,
+            Chain::Karura => 11,
+            Chain::Acala => 12,
+            Chain::Klaytn => 13,
+            Chain::Celo => 14,
+            Chain::Near => 15,
+            Chain::Moonbeam => 16,
+            Chain::Neon => 17,
+            Chain::Terra2 => 18,
+            Chain::Injective => 19,
+            Chain::Osmosis => 20,
+            Chain::Sui => 21,
+            Chain::Aptos => 22,
+            Chain::Arbitrum => 23,
+            Chain::Optimism => 24,
+            Chain::Gnosis => 25,
+            Chain::Pythnet => 26,
+            Chain::Xpla => 28,
+            Chain::Wormchain => 3104,
+            Chain::Unknown(c) => c,
+        }
+    }
+}
+
+/// Chains are rendered using the same names as the guardian node, unknown chains are rendered as
+/// their numeric ID.
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Chain::All => f.write_str("all"),
+            Chain::Solana => f.write_str("solana"),
+            Chain::Ethereum => f.write_str("ethereum"),
+            Chain::Terra => f.write_str("terra"),
+            Chain::Binance => f.write_str("bsc"),
+            Chain::Polygon => f.write_str("polygon"),
+            Chain::AVAX => f.write_str("avalanche"),
+            Chain::Oasis => f.write_str("oasis"),
+            Chain::Algorand => f.write_str("algorand"),
+            Chain::Aurora => f.write_str("aurora"),
+            Chain::Fantom => f.write_str("fantom"),
+            Chain::Karura => f.write_str("karura"),
+            Chain::Acala => f.write_str("acala"),
+            Chain::Klaytn => f.write_str("klaytn"),
+            Chain::Celo => f.write_str("celo"),
+            Chain::Near => f.write_str("near"),
+            Chain::Moonbeam => f.write_str("moonbeam"),
+            Chain::Neon => f.write_str("neon"),
+            Chain::Terra2 => f.write_str("terra2"),
+            Chain::Injective => f.write_str("injective"),
+            Chain::Osmosis => f.write_str("osmosis"),
+            Chain::Sui => f.write_str("sui"),
+            Chain::Aptos => f.write_str("aptos"),
+            Chain::Arbitrum => f.write_str("arbitrum"),
+            Chain::Optimism => f.write_str("optimism"),
+            Chain::Gnosis => f.write_str("gnosis"),
+            Chain::Pythnet => f.write_str("pythnet"),
+            Chain::Xpla => f.write_str("xpla"),
+            Chain::Wormchain => f.write_str("wormchain"),
+            Chain::Unknown(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+/// Parses the names produced by `Display`, case insensitively, as well as numeric chain IDs.
+impl FromStr for Chain {
+    type Err = WormholeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(Chain::All),
+            "solana" => Ok(Chain::Solana),
+            "ethereum" => Ok(Chain::Ethereum),
+            "terra" => Ok(Chain::Terra),
+            "bsc" | "binance" => Ok(Chain::Binance),
+            "polygon" => Ok(Chain::Polygon),
+            "avalanche" | "avax" => Ok(Chain::AVAX),
+            "oasis" => Ok(Chain::Oasis),
+            "algorand" => Ok(Chain::Algorand),
+            "aurora" => Ok(Chain::Aurora),
+            "fantom" => Ok(Chain::Fantom),
+            "karura" => Ok(Chain::Karura),
+            "acala" => Ok(Chain::Acala),
+            "klaytn" => Ok(Chain::Klaytn),
+            "celo" => Ok(Chain::Celo),
+            "near" => Ok(Chain::Near),
+            "moonbeam" => Ok(Chain::Moonbeam),
+            "neon" => Ok(Chain::Neon),
+            "terra2" => Ok(Chain::Terra2),
+            "injective" => Ok(Chain::Injective),
+            "osmosis" => Ok(Chain::Osmosis),
+            "sui" => Ok(Chain::Sui),
+            "aptos" => Ok(Chain::Aptos),
+            "arbitrum" => Ok(Chain::Arbitrum),
+            "optimism" => Ok(Chain::Optimism),
+            "gnosis" => Ok(Chain::Gnosis),
+            "pythnet" => Ok(Chain::Pythnet),
+            "xpla" => Ok(Chain::Xpla),
+            "wormchain" => Ok(Chain::Wormchain),
+            other => other
+                .parse::<u16>()
+                .map(Chain::from)
+                .map_err(|_| WormholeError::InvalidChain),
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::Chain;
+
+    #[test]
+    fn test_chain_id_round_trip() {
+        for id in 0..=u16::MAX {
+            let chain = Chain::from(id);
+            assert_eq!(u16::from(chain), id);
+            assert_eq!(chain.to_string().parse::<Chain>().unwrap(), chain);
+        }
+    }
+
+    #[test]
+    fn test_chain_names() {
+        assert_eq!(Chain::from(15), Chain::Near);
+        assert_eq!(Chain::from(20), Chain::Osmosis);
+        assert_eq!(Chain::from(25), Chain::Gnosis);
+        assert_eq!(Chain::from(27), Chain::Unknown(27));
+        assert_eq!(Chain::Near.to_string(), "near");
+        assert_eq!(Chain::Gnosis.to_string(), "gnosis");
+        assert_eq!(Chain::Unknown(27).to_string(), "27");
+        assert_eq!("Solana".parse::<Chain>().unwrap(), Chain::Solana);
+        assert_eq!("15".parse::<Chain>().unwrap(), Chain::Near);
+        assert!("notachain".parse::<Chain>().is_err());
     }
 }
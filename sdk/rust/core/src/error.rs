@@ -8,15 +8,78 @@ macro_rules! require {
     };
 }
 
-/// This ErrorCode maps to the nom ParseError, we use an integer because the library is deprecating
-/// the current error type, so we should avoid depending on it for now.
-type ErrorCode = usize;
+use core::fmt;
 
 #[derive(Debug)]
 pub enum WormholeError {
+    AlreadyClaimed,
+    InvalidAccount,
+    InvalidAddress,
+    InvalidChain,
+    InvalidEmitter,
     InvalidGovernanceAction,
     InvalidGovernanceChain,
     InvalidGovernanceModule,
+    InvalidGuardianIndex,
+    InvalidGuardianOrder,
+    InvalidGuardianSetIndex,
+    InvalidNetwork,
+    InvalidObservation,
+    InvalidSecretKey,
+    InvalidSignature,
+    NoQuorum,
     DeserializeFailed,
-    ParseError(ErrorCode),
+    SerializeFailed,
+    ParseError(ParseError),
+}
+
+/// Describes where and why parsing of wire data failed. This does not depend on nom's error types
+/// as the library is deprecating the current error type, so we should avoid depending on it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Name of the field that could not be parsed, such as "emitter_chain" or "amount".
+    pub field: &'static str,
+
+    /// Byte offset into the input at which parsing failed.
+    pub offset: usize,
+
+    pub reason: ParseErrorReason,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorReason {
+    /// The input ended before the field could be read.
+    Truncated { expected: usize, actual: usize },
+
+    /// The field holds a value that is not allowed, such as an unexpected payload ID.
+    InvalidValue,
+
+    /// The field is expected to hold UTF-8 but does not.
+    InvalidUtf8,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let field = if self.field.is_empty() {
+            "<unknown>"
+        } else {
+            self.field
+        };
+
+        match self.reason {
+            ParseErrorReason::Truncated { expected, actual } => write!(
+                f,
+                "field {} at offset {} is truncated: expected {} bytes, found {}",
+                field, self.offset, expected, actual
+            ),
+            ParseErrorReason::InvalidValue => {
+                write!(f, "field {} at offset {} is invalid", field, self.offset)
+            }
+            ParseErrorReason::InvalidUtf8 => write!(
+                f,
+                "field {} at offset {} is not valid UTF-8",
+                field, self.offset
+            ),
+        }
+    }
 }
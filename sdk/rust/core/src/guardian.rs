@@ -0,0 +1,73 @@
+//! Guardians are the set of nodes that observe and sign messages on the Wormhole network. A VAA is
+//! only considered valid if it carries signatures from a quorum of the guardian set that was
+//! active at the time it was produced.
+
+use alloc::vec::Vec;
+
+/// Guardians are identified by the last 20 bytes of the Keccak hash of their secp256k1 public key,
+/// the same scheme used for Ethereum addresses.
+pub type GuardianAddress = [u8; 20];
+
+/// A guardian set as stored by each of the Wormhole core contracts. The position of a guardian in
+/// `addresses` is the guardian index found in the first byte of each VAA `Signature`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GuardianSet {
+    pub index: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_list"))]
+    pub addresses: Vec<GuardianAddress>,
+}
+
+impl GuardianSet {
+    pub fn new(index: u32, addresses: Vec<GuardianAddress>) -> Self {
+        GuardianSet { index, addresses }
+    }
+
+    /// The minimum number of signatures required for a VAA to be considered valid, this is two
+    /// thirds of the guardian set plus one and must match the quorum rule of the core contracts.
+    pub fn quorum(&self) -> usize {
+        ((self.addresses.len() * 10 / 3) * 2) / 10 + 1
+    }
+}
+
+/// Successful result of verifying a VAA against a `GuardianSet`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerifiedSignatures {
+    /// Guardian set the VAA was verified against.
+    pub guardian_set_index: u32,
+
+    /// Indices of the guardians that signed the VAA, in strictly increasing order.
+    pub signers: Vec<u8>,
+
+    /// Number of signatures that were required to reach quorum.
+    pub quorum: usize,
+}
+
+impl VerifiedSignatures {
+    /// Check whether the guardian at `index` contributed a valid signature.
+    pub fn signed_by(&self, index: u8) -> bool {
+        self.signers.contains(&index)
+    }
+}
+
+/// Recover the guardian address that produced a secp256k1 signature over a 32 byte message. The
+/// signature is the 65 byte `r || s || v` portion of a VAA `Signature`.
+pub fn recover_guardian(message: &[u8; 32], signature: &[u8]) -> Option<GuardianAddress> {
+    use sha3::Digest;
+
+    if signature.len() != 65 {
+        return None;
+    }
+
+    let message = libsecp256k1::Message::parse(message);
+    let recovery_id = libsecp256k1::RecoveryId::parse(signature[64]).ok()?;
+    let signature = libsecp256k1::Signature::parse_standard_slice(&signature[..64]).ok()?;
+    let key = libsecp256k1::recover(&message, &signature, &recovery_id).ok()?;
+
+    // Guardian addresses are derived from the uncompressed public key without its 0x04 prefix.
+    let hash = sha3::Keccak256::digest(&key.serialize()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    Some(address)
+}
@@ -1,12 +1,32 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(unused_results)]
 
+#[cfg(not(feature = "alloc"))]
+compile_error!("wormhole-core requires the `alloc` feature");
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+pub use address::*;
 pub use chain::*;
 pub use error::*;
+pub use guardian::*;
+pub use network::*;
 pub use vaa::*;
 
+pub mod address;
 pub mod chain;
+pub mod guardian;
+pub mod network;
 pub mod vaa;
 
+#[cfg(feature = "signing")]
+pub mod signing;
+
+#[cfg(feature = "serde")]
+mod serde_utils;
+
 #[macro_use]
 pub mod error;
 
@@ -15,20 +35,26 @@ pub mod error;
 /// while still maintaining the ability to render.
 ///
 /// This should be used to parse any Text-over-Wormhole fields that are meant to be human readable.
-pub(crate) fn parse_fixed_utf8<T: AsRef<[u8]>, const N: usize>(s: T) -> Option<String> {
+pub(crate) fn parse_fixed_utf8<const N: usize>(s: &[u8; N]) -> String {
     use bstr::ByteSlice;
-    use std::io::Cursor;
-    use std::io::Read;
 
-    // Read Bytes.
-    let mut cursor = Cursor::new(s.as_ref());
-    let mut buffer = vec![0u8; N];
-    cursor.read_exact(&mut buffer).ok()?;
+    // Strip null padding.
+    let mut buffer = s.to_vec();
     buffer.retain(|&c| c != 0);
 
     // Attempt UTF-8 Decoding. Stripping invalid Unicode characters (0xFFFD).
     let mut buffer: Vec<char> = buffer.chars().collect();
     buffer.retain(|&c| c != '\u{FFFD}');
 
-    Some(buffer.iter().collect())
+    buffer.iter().collect()
+}
+
+/// Helper method that writes a UTF-8 string into a fixed size, right zero-padded byte field. The
+/// string is truncated at the byte level if it does not fit, which matches the behaviour of the
+/// bridge contracts; `parse_fixed_utf8` strips any invalid trailing bytes produced this way.
+pub(crate) fn serialize_fixed_utf8<const N: usize>(s: &str) -> [u8; N] {
+    let mut buffer = [0u8; N];
+    let count = ::core::cmp::min(N, s.len());
+    buffer[..count].copy_from_slice(&s.as_bytes()[..count]);
+    buffer
 }
@@ -0,0 +1,399 @@
+//! Wormhole is deployed to several networks, each with its own guardian set and contract
+//! deployments. This module maps each network and chain to the addresses of the Wormhole contracts
+//! deployed there, so that a single binary can target any network selected at runtime.
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Chain, WormholeError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+
+    /// The local Tilt devnet.
+    Devnet,
+}
+
+/// Addresses of the Wormhole contracts on a single chain. Addresses are kept in the native format
+/// of the chain, such as base58 on Solana, bech32 on CosmWasm chains, account IDs on NEAR and
+/// application IDs on Algorand. Contracts that are not deployed are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Contracts {
+    pub core: Option<&'static str>,
+    pub token_bridge: Option<&'static str>,
+    pub nft_bridge: Option<&'static str>,
+}
+
+fn contracts(
+    core: Option<&'static str>,
+    token_bridge: Option<&'static str>,
+    nft_bridge: Option<&'static str>,
+) -> Contracts {
+    Contracts {
+        core,
+        token_bridge,
+        nft_bridge,
+    }
+}
+
+impl Network {
+    /// Addresses of the Wormhole contracts deployed to `chain` on this network.
+    pub fn contracts(self, chain: Chain) -> Contracts {
+        match (self, chain) {
+            (Network::Mainnet, Chain::Solana) => contracts(
+                Some("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth"),
+                Some("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb"),
+                Some("WnFt12ZrnzZrFZkt2xsNsaNWoQribnuQ5B5FrDbwDhD"),
+            ),
+            (Network::Mainnet, Chain::Ethereum) => contracts(
+                Some("0x98f3c9e6E3fAce36bAAd05FE09d375Ef1464288B"),
+                Some("0x3ee18B2214AFF97000D974cf647E7C347E8fa585"),
+                Some("0x6FFd7EdE62328b3Af38FCD61461Bbfc52F5651fE"),
+            ),
+            (Network::Mainnet, Chain::Terra) => contracts(
+                Some("terra1dq03ugtd40zu9hcgdzrsq6z2z4hwhc9tqk2uy5"),
+                Some("terra10nmmwe8r3g99a9newtqa7a75xfgs2e8z87r2sf"),
+                None,
+            ),
+            (Network::Mainnet, Chain::Binance) => contracts(
+                Some("0x98f3c9e6E3fAce36bAAd05FE09d375Ef1464288B"),
+                Some("0xB6F6D86a8f9879A9c87f643768d9efc38c1Da6E7"),
+                Some("0x5a58505a96D1dbf8dF91cB21B54419FC36e93fdE"),
+            ),
+            (Network::Mainnet, Chain::Polygon) => contracts(
+                Some("0x7A4B5a56256163F07b2C80A7cA55aBE66c4ec4d7"),
+                Some("0x5a58505a96D1dbf8dF91cB21B54419FC36e93fdE"),
+                Some("0x90BBd86a6Fe93D3bc3ed6335935447E75fAb7fCf"),
+            ),
+            (Network::Mainnet, Chain::AVAX) => contracts(
+                Some("0x54a8e5f9c4CbA08F9943965859F6c34eAF03E26c"),
+                Some("0x0e082F06FF657D94310cB8cE8B0D9a04541d8052"),
+                Some("0xf7B6737Ca9c4e08aE573F75A97B73D7a813f5De5"),
+            ),
+            (Network::Mainnet, Chain::Oasis) => contracts(
+                Some("0xfE8cD454b4A1CA468B57D79c0cc77Ef5B6f64585"),
+                Some("0x5848C791e09901b40A9Ef749f2a6735b418d7564"),
+                Some("0x04952D522Ff217f40B5Ef3cbF659EcA7b952a6c1"),
+            ),
+            (Network::Mainnet, Chain::Algorand) => {
+                contracts(Some("842125965"), Some("842126029"), None)
+            }
+            (Network::Mainnet, Chain::Aurora) => contracts(
+                Some("0xa321448d90d4e5b0A732867c18eA198e75CAC48E"),
+                Some("0x51b5123a7b0F9b2bA265f9c4C8de7D78D52f510F"),
+                Some("0x6dcC0484472523ed9Cdc017F711Bcbf909789284"),
+            ),
+            (Network::Mainnet, Chain::Fantom) => contracts(
+                Some("0x126783A6Cb203a3E35344528B26ca3a0489a1485"),
+                Some("0x7C9Fc5741288cDFdD83CeB07f3ea7e22618D79D2"),
+                Some("0xA9c7119aBDa80d4a4E0C06C8F4d8cF5893234535"),
+            ),
+            (Network::Mainnet, Chain::Karura) => contracts(
+                Some("0xa321448d90d4e5b0A732867c18eA198e75CAC48E"),
+                Some("0xae9d7fe007b3327AA64A32824Aaac52C42a6E624"),
+                Some("0xb91e3638F82A1fACb28690b37e3aAE45d2c33808"),
+            ),
+            (Network::Mainnet, Chain::Acala) => contracts(
+                Some("0xa321448d90d4e5b0A732867c18eA198e75CAC48E"),
+                Some("0xae9d7fe007b3327AA64A32824Aaac52C42a6E624"),
+                Some("0xb91e3638F82A1fACb28690b37e3aAE45d2c33808"),
+            ),
+            (Network::Mainnet, Chain::Klaytn) => contracts(
+                Some("0x0C21603c4f3a6387e241c0091A7EA39E43E90bb7"),
+                Some("0x5b08ac39EAED75c0439FC750d9FE7E1F9dD0193F"),
+                Some("0x3c3c561757BAa0b78c5C025CdEAa4ee24C1dFfEf"),
+            ),
+            (Network::Mainnet, Chain::Celo) => contracts(
+                Some("0xa321448d90d4e5b0A732867c18eA198e75CAC48E"),
+                Some("0x796Dff6D74F3E27060B71255Fe517BFb23C93eed"),
+                Some("0xA6A377d75ca5c9052c9a77ED1e865Cc25Bd97bf3"),
+            ),
+            (Network::Mainnet, Chain::Near) => contracts(
+                Some("contract.wormhole_crypto.near"),
+                Some("contract.portalbridge.near"),
+                None,
+            ),
+            (Network::Mainnet, Chain::Aptos) => contracts(
+                Some("0x5bc11445584a763c1fa7ed39081f1b920954da14e04b32440cba863d03e19625"),
+                Some("0x576410486a2da45eee6c949c995670112ddf2fbeedab20350d506328eefc9d4f"),
+                None,
+            ),
+            (Network::Mainnet, Chain::Moonbeam) => contracts(
+                Some("0xC8e2b0cD52Cf01b0Ce87d389Daa3d414d4cE29f3"),
+                Some("0xb1731c586ca89a23809861c6103f0b96b3f57d92"),
+                Some("0x453cfbe096c0f8d763e8c5f24b441097d577bde2"),
+            ),
+            (Network::Mainnet, Chain::Terra2) => contracts(
+                Some("terra12mrnzvhx3rpej6843uge2yyfppfyd3u9c3uq223q8sl48huz9juqffcnhp"),
+                Some("terra153366q50k7t8nn7gec00hg66crnhkdggpgdtaxltaq6xrutkkz3s992fw9"),
+                None,
+            ),
+            (Network::Mainnet, Chain::Arbitrum) => contracts(
+                Some("0xa5f208e072434bC67592E4C49C1B991BA79BCA46"),
+                Some("0x0b2402144Bb366A632D14B83F244D2e0e21bD39c"),
+                Some("0x3dD14D553cFD986EAC8e3bddF629d82073e188c8"),
+            ),
+            (Network::Mainnet, Chain::Optimism) => contracts(
+                Some("0xEe91C335eab126dF5fDB3797EA9d6aD93aeC9722"),
+                Some("0x1D68124e65faFC907325e3EDbF8c4d84499DAa8b"),
+                Some("0xfE8cD454b4A1CA468B57D79c0cc77Ef5B6f64585"),
+            ),
+            (Network::Mainnet, Chain::Gnosis) => contracts(
+                Some("0xa321448d90d4e5b0A732867c18eA198e75CAC48E"),
+                None,
+                None,
+            ),
+            (Network::Mainnet, Chain::Pythnet) => contracts(
+                Some("H3fxXJ86ADW2PNuDDmZJg6mzTtPxkYCpNuQUTgmJ7AjU"),
+                None,
+                None,
+            ),
+            (Network::Mainnet, Chain::Xpla) => contracts(
+                Some("xpla1jn8qmdda5m6f6fqu9qv46rt7ajhklg40ukpqchkejcvy8x7w26cqxamv3w"),
+                Some("xpla137w0wfch2dfmz7jl2ap8pcmswasj8kg06ay4dtjzw7tzkn77ufxqfw7acv"),
+                None,
+            ),
+            (Network::Testnet, Chain::Solana) => contracts(
+                Some("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5"),
+                Some("DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe"),
+                Some("2rHhojZ7hpu1zA91nvZmT8TqWWvMcKmmNBCr2mKTtMq4"),
+            ),
+            (Network::Testnet, Chain::Terra) => contracts(
+                Some("terra1pd65m0q9tl3v8znnz5f5ltsfegyzah7g42cx5v"),
+                Some("terra1pseddrv0yfsn76u4zxrjmtf45kdlmalswdv39a"),
+                None,
+            ),
+            (Network::Testnet, Chain::Ethereum) => contracts(
+                Some("0x706abc4E45D419950511e474C7B9Ed348A4a716c"),
+                Some("0xF890982f9310df57d00f659cf4fd87e65adEd8d7"),
+                Some("0xD8E4C2DbDd2e2bd8F1336EA691dBFF6952B1a6eB"),
+            ),
+            (Network::Testnet, Chain::Binance) => contracts(
+                Some("0x68605AD7b15c732a30b1BbC62BE8F2A509D74b4D"),
+                Some("0x9dcF9D205C9De35334D646BeE44b2D2859712A09"),
+                Some("0xcD16E5613EF35599dc82B24Cb45B5A93D779f1EE"),
+            ),
+            (Network::Testnet, Chain::Polygon) => contracts(
+                Some("0x0CBE91CF822c73C2315FB05100C2F714765d5c20"),
+                Some("0x377D55a7928c046E18eEbb61977e714d2a76472a"),
+                Some("0x51a02d0dcb5e52F5b92bdAA38FA013C91c7309A9"),
+            ),
+            (Network::Testnet, Chain::AVAX) => contracts(
+                Some("0x7bbcE28e64B3F8b84d876Ab298393c38ad7aac4C"),
+                Some("0x61E44E506Ca5659E6c0bba9b678586fA2d729756"),
+                Some("0xD601BAf2EEE3C028344471684F6b27E789D9075D"),
+            ),
+            (Network::Testnet, Chain::Oasis) => contracts(
+                Some("0xc1C338397ffA53a2Eb12A7038b4eeb34791F8aCb"),
+                Some("0x88d8004A9BdbfD9D28090A02010C19897a29605c"),
+                Some("0xC5c25B41AB0b797571620F5204Afa116A44c0ebA"),
+            ),
+            (Network::Testnet, Chain::Algorand) => {
+                contracts(Some("86525623"), Some("86525641"), None)
+            }
+            (Network::Testnet, Chain::Aurora) => contracts(
+                Some("0xBd07292de7b505a4E803CEe286184f7Acf908F5e"),
+                Some("0xD05eD3ad637b890D68a854d607eEAF11aF456fba"),
+                Some("0x8F399607E9BA2405D87F5f3e1B78D950b44b2e24"),
+            ),
+            (Network::Testnet, Chain::Fantom) => contracts(
+                Some("0x1BB3B4119b7BA9dfad76B0545fb3F531383c3bB7"),
+                Some("0x599CEa2204B4FaECd584Ab1F2b6aCA137a0afbE8"),
+                Some("0x63eD9318628D26BdCB15df58B53BB27231D1B227"),
+            ),
+            (Network::Testnet, Chain::Karura) => contracts(
+                Some("0xE4eacc10990ba3308DdCC72d985f2a27D20c7d03"),
+                Some("0xd11De1f930eA1F7Dd0290Fe3a2e35b9C91AEFb37"),
+                Some("0x0A693c2D594292B6Eb89Cb50EFe4B0b63Dd2760D"),
+            ),
+            (Network::Testnet, Chain::Acala) => contracts(
+                Some("0x4377B49d559c0a9466477195C6AdC3D433e265c0"),
+                Some("0xebA00cbe08992EdD08ed7793E07ad6063c807004"),
+                Some("0x96f1335e0AcAB3cfd9899B30b2374e25a2148a6E"),
+            ),
+            (Network::Testnet, Chain::Klaytn) => contracts(
+                Some("0x1830CC6eE66c84D2F177B94D544967c774E624cA"),
+                Some("0xC7A13BE098720840dEa132D860fDfa030884b09A"),
+                Some("0x94c994fC51c13101062958b567e743f1a04432dE"),
+            ),
+            (Network::Testnet, Chain::Celo) => contracts(
+                Some("0x88505117CA88e7dd2eC6EA1E13f0948db2D50D56"),
+                Some("0x05ca6037eC51F8b712eD2E6Fa72219FEaE74E153"),
+                Some("0xaCD8190F647a31E56A656748bC30F69259f245Db"),
+            ),
+            (Network::Testnet, Chain::Near) => contracts(
+                Some("wormhole.wormhole.testnet"),
+                Some("token.wormhole.testnet"),
+                None,
+            ),
+            (Network::Testnet, Chain::Injective) => contracts(
+                Some("inj1xx3aupmgv3ce537c0yce8zzd3sz567syuyedpg"),
+                Some("inj1q0e70vhrv063eah90mu97sazhywmeegp7myvnh"),
+                None,
+            ),
+            (Network::Testnet, Chain::Osmosis) => contracts(
+                Some("osmo1hggkxr0hpw83f8vuft7ruvmmamsxmwk2hzz6nytdkzyup9krt0dq27sgyx"),
+                None,
+                None,
+            ),
+            (Network::Testnet, Chain::Aptos) => contracts(
+                Some("0x5bc11445584a763c1fa7ed39081f1b920954da14e04b32440cba863d03e19625"),
+                Some("0x576410486a2da45eee6c949c995670112ddf2fbeedab20350d506328eefc9d4f"),
+                None,
+            ),
+            (Network::Testnet, Chain::Moonbeam) => contracts(
+                Some("0xa5B7D85a8f27dd7907dc8FdC21FA5657D5E2F901"),
+                Some("0xbc976D4b9D57E57c3cA52e1Fd136C45FF7955A96"),
+                Some("0x98A0F4B96972b32Fcb3BD03cAeB66A44a6aB9Edb"),
+            ),
+            (Network::Testnet, Chain::Neon) => contracts(
+                Some("0x6b9C8671cdDC8dEab9c719bB87cBd3e782bA6a35"),
+                Some("0xC7A204bDBFe983FCD8d8E61D02b475D4073fF97e"),
+                Some("0x23908A62110e21C04F3A4e011d24F901F911744A"),
+            ),
+            (Network::Testnet, Chain::Terra2) => contracts(
+                Some("terra19nv3xr5lrmmr7egvrk2kqgw4kcn43xrtd5g0mpgwwvhetusk4k7s66jyv0"),
+                Some("terra1c02vds4uhgtrmcw7ldlg75zumdqxr8hwf7npseuf2h58jzhpgjxsgmwkvk"),
+                None,
+            ),
+            (Network::Testnet, Chain::Arbitrum) => contracts(
+                Some("0xC7A204bDBFe983FCD8d8E61D02b475D4073fF97e"),
+                Some("0x23908A62110e21C04F3A4e011d24F901F911744A"),
+                Some("0xEe3dB83916Ccdc3593b734F7F2d16D630F39F1D0"),
+            ),
+            (Network::Testnet, Chain::Optimism) => contracts(
+                Some("0x6b9C8671cdDC8dEab9c719bB87cBd3e782bA6a35"),
+                Some("0xC7A204bDBFe983FCD8d8E61D02b475D4073fF97e"),
+                Some("0x23908A62110e21C04F3A4e011d24F901F911744A"),
+            ),
+            (Network::Testnet, Chain::Gnosis) => contracts(
+                Some("0xE4eacc10990ba3308DdCC72d985f2a27D20c7d03"),
+                None,
+                None,
+            ),
+            (Network::Testnet, Chain::Pythnet) => contracts(
+                Some("EUrRARh92Cdc54xrDn6qzaqjA77NRrCcfbr8kPwoTL4z"),
+                None,
+                None,
+            ),
+            (Network::Testnet, Chain::Xpla) => contracts(
+                Some("xpla1upkjn4mthr0047kahvn0llqx4qpqfn75lnph4jpxfn8walmm8mqsanyy35"),
+                Some("xpla1kek6zgdaxcsu35nqfsyvs2t9vs87dqkkq6hjdgczacysjn67vt8sern93x"),
+                None,
+            ),
+            (Network::Devnet, Chain::Solana) => contracts(
+                Some("Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o"),
+                Some("B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE"),
+                Some("NFTWqJR8YnRVqPDvTJrYuLrQDitTG5AScqbeghi4zSA"),
+            ),
+            (Network::Devnet, Chain::Terra) => contracts(
+                Some("terra18vd8fpwxzck93qlwghaj6arh4p7c5n896xzem5"),
+                Some("terra10pyejy66429refv3g35g2t7am0was7ya7kz2a4"),
+                Some("terra1plju286nnfj3z54wgcggd4enwaa9fgf5kgrgzl"),
+            ),
+            (Network::Devnet, Chain::Ethereum) => contracts(
+                Some("0xC89Ce4735882C9F0f0FE26686c53074E09B0D550"),
+                Some("0x0290FB167208Af455bB137780163b7B7a9a10C16"),
+                Some("0x26b4afb60d6c903165150c6f0aa14f8016be4aec"),
+            ),
+            (Network::Devnet, Chain::Binance) => contracts(
+                Some("0xC89Ce4735882C9F0f0FE26686c53074E09B0D550"),
+                Some("0x0290FB167208Af455bB137780163b7B7a9a10C16"),
+                Some("0x26b4afb60d6c903165150c6f0aa14f8016be4aec"),
+            ),
+            (Network::Devnet, Chain::Algorand) => contracts(Some("4"), Some("6"), None),
+            (Network::Devnet, Chain::Near) => {
+                contracts(Some("wormhole.test.near"), Some("token.test.near"), None)
+            }
+            (Network::Devnet, Chain::Aptos) => contracts(
+                Some("0xde0036a9600559e295d5f6802ef6f3f802f510366e0c23912b0655d972166017"),
+                Some("0x84a5f374d29fc77e370014dce4fd6a55b58ad608de8074b0be5571701724da31"),
+                None,
+            ),
+            (Network::Devnet, Chain::Terra2) => contracts(
+                Some("terra14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9ssrc8au"),
+                Some("terra1nc5tatafv6eyq7llkr2gv50ff9e22mnf70qgjlv737ktmt4eswrquka9l6"),
+                None,
+            ),
+            (Network::Devnet, Chain::Wormchain) => contracts(
+                Some("wormhole1ap5vgur5zlgys8whugfegnn43emka567dtq0jl"),
+                Some("wormhole1zugu6cajc4z7ue29g9wnes9a5ep9cs7yu7rn3z"),
+                None,
+            ),
+            _ => Contracts::default(),
+        }
+    }
+
+    /// Address of the core bridge on `chain`, if deployed on this network.
+    pub fn core(self, chain: Chain) -> Option<&'static str> {
+        self.contracts(chain).core
+    }
+
+    /// Address of the token bridge on `chain`, if deployed on this network.
+    pub fn token_bridge(self, chain: Chain) -> Option<&'static str> {
+        self.contracts(chain).token_bridge
+    }
+
+    /// Address of the NFT bridge on `chain`, if deployed on this network.
+    pub fn nft_bridge(self, chain: Chain) -> Option<&'static str> {
+        self.contracts(chain).nft_bridge
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Network::Mainnet => f.write_str("mainnet"),
+            Network::Testnet => f.write_str("testnet"),
+            Network::Devnet => f.write_str("devnet"),
+        }
+    }
+}
+
+/// Parses the names produced by `Display`, case insensitively.
+impl FromStr for Network {
+    type Err = WormholeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" => Ok(Network::Mainnet),
+            "testnet" => Ok(Network::Testnet),
+            "devnet" => Ok(Network::Devnet),
+            _ => Err(WormholeError::InvalidNetwork),
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::Network;
+    use crate::Chain;
+
+    #[test]
+    fn test_network_contracts() {
+        assert_eq!(
+            Network::Mainnet.core(Chain::Solana),
+            Some("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth")
+        );
+        assert_eq!(
+            Network::Testnet.core(Chain::Near),
+            Some("wormhole.wormhole.testnet")
+        );
+        assert_eq!(
+            Network::Devnet.token_bridge(Chain::Terra),
+            Some("terra10pyejy66429refv3g35g2t7am0was7ya7kz2a4")
+        );
+        assert_eq!(Network::Mainnet.nft_bridge(Chain::Terra), None);
+        assert_eq!(Network::Mainnet.core(Chain::Unknown(9999)), None);
+    }
+
+    #[test]
+    fn test_network_names() {
+        for network in [Network::Mainnet, Network::Testnet, Network::Devnet] {
+            assert_eq!(network.to_string().parse::<Network>().unwrap(), network);
+        }
+        assert_eq!("MAINNET".parse::<Network>().unwrap(), Network::Mainnet);
+        assert!("localnet".parse::<Network>().is_err());
+    }
+}
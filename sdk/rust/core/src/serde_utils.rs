@@ -0,0 +1,143 @@
+//! Helpers for the optional serde representation of Wormhole types. The representation is meant to
+//! be stable and readable as JSON: byte strings such as addresses, hashes and payloads are hex
+//! encoded, 256 bit integers are decimal strings and chains are rendered by name.
+
+use core::convert::TryFrom;
+use core::str::FromStr;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use primitive_types::U256;
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+use crate::Chain;
+
+fn decode<T: TryFrom<Vec<u8>>, E: Error>(s: &str) -> Result<T, E> {
+    let bytes = hex::decode(s).map_err(E::custom)?;
+    let len = bytes.len();
+    T::try_from(bytes).map_err(|_| E::custom(format!("unexpected byte length {}", len)))
+}
+
+/// Hex encoding for fixed and variable length byte strings, used with `#[serde(with = ...)]`.
+pub(crate) mod hex_bytes {
+    use super::*;
+
+    pub fn serialize<T: AsRef<[u8]>, S: Serializer>(v: &T, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(v))
+    }
+
+    pub fn deserialize<'de, T, D>(d: D) -> Result<T, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        decode(&String::deserialize(d)?)
+    }
+}
+
+/// Hex encoding for lists of fixed length byte strings, such as signatures and guardian addresses.
+pub(crate) mod hex_list {
+    use super::*;
+
+    pub fn serialize<T: AsRef<[u8]>, S: Serializer>(v: &[T], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(v.iter().map(hex::encode))
+    }
+
+    pub fn deserialize<'de, T, D>(d: D) -> Result<Vec<T>, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(d)?
+            .iter()
+            .map(|s| decode(s))
+            .collect()
+    }
+}
+
+/// Decimal string encoding for 256 bit integers, which do not fit in a JSON number.
+pub(crate) mod u256_dec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(v: &U256, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(v)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<U256, D::Error> {
+        let s = String::deserialize(d)?;
+        U256::from_dec_str(&s).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+/// Chains are serialized by name, see `Chain`'s `Display` and `FromStr`.
+impl Serialize for Chain {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Chain {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        Chain::from_str(&s).map_err(|_| D::Error::custom(format!("unknown chain {:?}", s)))
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use crate::vaa::token::Transfer;
+    use crate::{Chain, Payload, VAA};
+
+    #[test]
+    fn test_vaa_json() {
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let vaa = VAA::from_bytes(vaa).unwrap();
+
+        let json = serde_json::to_value(&vaa).unwrap();
+        assert_eq!(json["emitter_chain"], "solana");
+        assert_eq!(
+            json["emitter_address"],
+            "0000000000000000000000000000000000000000000000000000000000000004"
+        );
+        assert_eq!(json["signatures"][0], hex::encode(vaa.signatures[0]));
+        assert_eq!(json["payload"], hex::encode(&vaa.payload));
+        assert_eq!(serde_json::from_value::<VAA>(json).unwrap(), vaa);
+
+        // Governance payloads carry the header, with the chain rendered by name.
+        let json = serde_json::to_value(vaa.parse_payload()).unwrap();
+        assert_eq!(json["TokenRegisterChain"][0]["chains"], "all");
+        assert_eq!(json["TokenRegisterChain"][1]["emitter"], "solana");
+    }
+
+    #[test]
+    fn test_transfer_json() {
+        let transfer = Transfer {
+            amount: u128::MAX.into(),
+            token_address: [1u8; 32],
+            token_chain: Chain::Ethereum,
+            to: [2u8; 32],
+            to_chain: Chain::Unknown(9999),
+            fee: 0.into(),
+        };
+
+        let json = serde_json::to_value(&transfer).unwrap();
+        assert_eq!(json["amount"], u128::MAX.to_string());
+        assert_eq!(json["fee"], "0");
+        assert_eq!(json["token_chain"], "ethereum");
+        assert_eq!(json["to_chain"], "9999");
+        assert_eq!(serde_json::from_value::<Transfer>(json).unwrap(), transfer);
+
+        let payload = Payload::Raw(vec![0xde, 0xad]);
+        let json = serde_json::to_string(&payload).unwrap();
+        assert_eq!(json, r#"{"Raw":"dead"}"#);
+        assert_eq!(serde_json::from_str::<Payload>(&json).unwrap(), payload);
+
+        // Addresses must decode to exactly the field width.
+        let mut json = serde_json::to_value(&transfer).unwrap();
+        json["to"] = "0102".into();
+        assert!(serde_json::from_value::<Transfer>(json).is_err());
+    }
+}
@@ -9,27 +9,36 @@
 //! includes parsers for the core VAA type. Programs targetting wormhole can use this module to
 //! parse and verify incoming VAA's securely.
 
+use ::core::convert::TryFrom;
 use nom::combinator::rest;
-use nom::error::{Error, ErrorKind};
-use nom::multi::{count, fill};
-use nom::number::complete::{u16, u32, u64, u8};
-use nom::number::Endianness;
+use nom::error::{context, ContextError, ErrorKind, ParseError as NomParseError};
 use nom::{Err, Finish, IResult};
-use std::convert::TryFrom;
+use primitive_types::U256;
+
+use alloc::string::String;
+use alloc::vec::Vec;
 
 use crate::{
-    require, Chain,
+    recover_guardian, require, Chain, GuardianSet, ParseError, ParseErrorReason,
+    VerifiedSignatures,
     WormholeError::{
         self, InvalidGovernanceAction, InvalidGovernanceChain, InvalidGovernanceModule,
+        InvalidGuardianIndex, InvalidGuardianOrder, InvalidGuardianSetIndex, InvalidSignature,
+        NoQuorum,
     },
 };
 
 // Import Module Specific VAAs.
 
+pub mod batch;
 pub mod core;
 pub mod nft;
+pub mod payload;
 pub mod token;
 
+pub use batch::BatchVAA;
+pub use payload::Payload;
+
 /// Signatures are typical ECDSA signatures prefixed with a Guardian position. These have the
 /// following byte layout:
 /// ```markdown
@@ -43,6 +52,14 @@ pub type Signature = [u8; 66];
 /// Ethereum addresses, are left zero padded to 32.
 pub type ForeignAddress = [u8; 32];
 
+/// Chain of the emitter that all governance VAA's must originate from.
+pub const GOVERNANCE_CHAIN: Chain = Chain::Solana;
+
+/// Address of the emitter that all governance VAA's must originate from.
+pub const GOVERNANCE_EMITTER: ForeignAddress = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4,
+];
+
 /// Fields on VAA's are all usually fixed bytestrings, however they often contain UTF-8. When
 /// parsed these result in `String` with the additional constraint that they are always equal or
 /// less to the underlying byte field.
@@ -51,157 +68,676 @@ type ShortUTFString = String;
 /// The core VAA itself. This structure is what is received by a contract on the receiving side of
 /// a wormhole message passing flow. The payload of the message must be parsed separately to the
 /// VAA itself as it is completely user defined.
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VAA {
     // Header
     pub version: u8,
     pub guardian_set_index: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_list"))]
     pub signatures: Vec<Signature>,
 
     // Body
     pub timestamp: u32,
     pub nonce: u32,
     pub emitter_chain: Chain,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub emitter_address: ForeignAddress,
     pub sequence: u64,
     pub consistency_level: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub payload: Vec<u8>,
 }
 
 /// Contains the hash, secp256k1 payload, and serialized digest of the VAA. These are used in
 /// various places in Wormhole codebases.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VAADigest {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub digest: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub hash: [u8; 32],
 }
 
 impl VAA {
     /// Given any argument treatable as a series of bytes, attempt to deserialize into a valid VAA.
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
-        match parse_vaa(input.as_ref()).finish() {
+        let input = input.as_ref();
+        match parse_vaa(input).finish() {
             Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+            Err(e) => Err(e.locate(input)),
         }
     }
 
+    /// Identify the payload of this VAA and parse it into the matching typed payload, falling back
+    /// to `Payload::Raw` for payloads that are not a known Wormhole standard.
+    pub fn parse_payload(&self) -> Payload {
+        Payload::from_bytes(&self.payload)
+    }
+
+    /// Serialize the VAA into the Wormhole wire format, this is the inverse of `from_bytes`. This
+    /// fails only if the VAA holds more signatures than can be encoded in the signature count.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WormholeError> {
+        let signature_count =
+            u8::try_from(self.signatures.len()).map_err(|_| WormholeError::SerializeFailed)?;
+
+        let mut v = Vec::new();
+        v.push(self.version);
+        v.extend_from_slice(&self.guardian_set_index.to_be_bytes());
+        v.push(signature_count);
+        for signature in &self.signatures {
+            v.extend_from_slice(signature);
+        }
+        v.extend_from_slice(&self.serialize_body());
+        Ok(v)
+    }
+
+    /// Serialize the body of the VAA. The body contains all fields that are covered by guardian
+    /// signatures, which is everything except the version, guardian set index and signatures.
+    pub fn serialize_body(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(51 + self.payload.len());
+        v.extend_from_slice(&self.timestamp.to_be_bytes());
+        v.extend_from_slice(&self.nonce.to_be_bytes());
+        v.extend_from_slice(&u16::from(self.emitter_chain).to_be_bytes());
+        v.extend_from_slice(&self.emitter_address);
+        v.extend_from_slice(&self.sequence.to_be_bytes());
+        v.push(self.consistency_level);
+        v.extend_from_slice(&self.payload);
+        v
+    }
+
     /// A VAA is distinguished by the unique hash of its deterministic components. This method
     /// returns a 256 bit Keccak hash of these components. This hash is utilised in all Wormhole
     /// components for identifying unique VAA's, including the bridge, modules, and core guardian
     /// software.
     pub fn digest(&self) -> Option<VAADigest> {
-        use byteorder::{BigEndian, WriteBytesExt};
         use sha3::Digest;
-        use std::io::{Cursor, Write};
 
         // Hash Deterministic Pieces
-        let body = {
-            let mut v = Cursor::new(Vec::new());
-            v.write_u32::<BigEndian>(self.timestamp).ok()?;
-            v.write_u32::<BigEndian>(self.nonce).ok()?;
-            v.write_u16::<BigEndian>(self.emitter_chain.clone() as u16)
-                .ok()?;
-            let _ = v.write(&self.emitter_address).ok()?;
-            v.write_u64::<BigEndian>(self.sequence).ok()?;
-            v.write_u8(self.consistency_level).ok()?;
-            let _ = v.write(&self.payload).ok()?;
-            v.into_inner()
-        };
+        let body = self.serialize_body();
 
         // We hash the body so that secp256k1 signatures are signing the hash instead of the body
         // within our contracts. We do this so we don't have to submit the entire VAA for signature
         // verification, only the hash.
         let hash: [u8; 32] = {
             let mut h = sha3::Keccak256::default();
-            let _ = h.write(body.as_slice()).unwrap();
+            h.update(body.as_slice());
             h.finalize().into()
         };
 
         Some(VAADigest { digest: body, hash })
     }
+
+    /// Guardians sign the hash of the body hash, rather than the body hash itself. This returns
+    /// the 32 byte message that each guardian signature commits to.
+    pub(crate) fn signing_hash(&self) -> [u8; 32] {
+        signing_hash(&self.serialize_body())
+    }
+
+    /// Verify the signatures on this VAA against a guardian set. Every signature must recover to
+    /// the guardian at its index, indices must be strictly increasing so that no guardian is
+    /// counted twice, and the number of signatures must reach the quorum of the guardian set.
+    pub fn verify(&self, guardian_set: &GuardianSet) -> Result<VerifiedSignatures, WormholeError> {
+        verify_signatures(
+            self.guardian_set_index,
+            self.signatures.iter(),
+            &self.signing_hash(),
+            guardian_set,
+        )
+    }
+}
+
+/// Guardians sign the Keccak hash of the body hash, this computes that message from a serialized
+/// body.
+fn signing_hash(body: &[u8]) -> [u8; 32] {
+    use sha3::Digest;
+    let hash = sha3::Keccak256::digest(body);
+    sha3::Keccak256::digest(&hash).into()
+}
+
+/// Shared signature verification for owned and borrowed VAA's, see `VAA::verify`.
+fn verify_signatures<'s>(
+    guardian_set_index: u32,
+    signatures: impl ExactSizeIterator<Item = &'s Signature>,
+    hash: &[u8; 32],
+    guardian_set: &GuardianSet,
+) -> Result<VerifiedSignatures, WormholeError> {
+    let quorum = guardian_set.quorum();
+    require!(
+        guardian_set_index == guardian_set.index,
+        InvalidGuardianSetIndex
+    );
+    require!(signatures.len() >= quorum, NoQuorum);
+
+    let mut signers: Vec<u8> = Vec::with_capacity(signatures.len());
+    for signature in signatures {
+        let index = signature[0];
+        let valid_order = !matches!(signers.last(), Some(&last) if index <= last);
+        require!(valid_order, InvalidGuardianOrder);
+
+        let guardian = guardian_set
+            .addresses
+            .get(index as usize)
+            .ok_or(InvalidGuardianIndex)?;
+
+        let signer = recover_guardian(hash, &signature[1..]).ok_or(InvalidSignature)?;
+        require!(&signer == guardian, InvalidSignature);
+        signers.push(index);
+    }
+
+    Ok(VerifiedSignatures {
+        guardian_set_index: guardian_set.index,
+        signers,
+        quorum,
+    })
+}
+
+/// A VAA that borrows its signatures and payload from the buffer it was parsed from rather than
+/// copying them, for consumers such as relayers that inspect large numbers of VAA's and only keep
+/// a few. Parsing performs no allocation, and `to_vaa` converts to an owned `VAA` when needed.
+///
+/// ```rust
+/// use wormhole_core::{VAABuilder, VAARef};
+///
+/// let bytes = VAABuilder::new().sequence(1).build().to_bytes().unwrap();
+/// let vaa = VAARef::from_bytes(&bytes).unwrap();
+/// assert_eq!(vaa.sequence(), 1);
+/// assert_eq!(vaa.to_vaa().to_bytes().unwrap(), bytes);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VAARef<'a> {
+    version: u8,
+    guardian_set_index: u32,
+    signatures: &'a [u8],
+    body: &'a [u8],
+    timestamp: u32,
+    nonce: u32,
+    emitter_chain: Chain,
+    emitter_address: &'a ForeignAddress,
+    sequence: u64,
+    consistency_level: u8,
+    payload: &'a [u8],
+}
+
+impl<'a> VAARef<'a> {
+    /// Parse a VAA without copying, the result borrows from `input`.
+    pub fn from_bytes(input: &'a [u8]) -> Result<Self, WormholeError> {
+        match parse_vaa_ref(input).finish() {
+            Ok(input) => Ok(input.1),
+            Err(e) => Err(e.locate(input)),
+        }
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn guardian_set_index(&self) -> u32 {
+        self.guardian_set_index
+    }
+
+    pub fn signature_count(&self) -> usize {
+        self.signatures.len() / 66
+    }
+
+    /// Iterate over the signatures in the order they appear in the VAA.
+    pub fn signatures(&self) -> impl ExactSizeIterator<Item = &'a Signature> {
+        // The signature field is always a multiple of 66 bytes, so every chunk converts.
+        self.signatures
+            .chunks_exact(66)
+            .map(|signature| <&Signature>::try_from(signature).unwrap())
+    }
+
+    /// The serialized body, which is the portion of the VAA covered by guardian signatures.
+    pub fn body(&self) -> &'a [u8] {
+        self.body
+    }
+
+    pub fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    pub fn nonce(&self) -> u32 {
+        self.nonce
+    }
+
+    pub fn emitter_chain(&self) -> Chain {
+        self.emitter_chain
+    }
+
+    pub fn emitter_address(&self) -> &'a ForeignAddress {
+        self.emitter_address
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn consistency_level(&self) -> u8 {
+        self.consistency_level
+    }
+
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+
+    /// See `VAA::parse_payload`.
+    pub fn parse_payload(&self) -> Payload {
+        Payload::from_bytes(self.payload)
+    }
+
+    /// See `VAA::digest`, the hash is computed directly over the borrowed body.
+    pub fn digest(&self) -> Option<VAADigest> {
+        use sha3::Digest;
+        Some(VAADigest {
+            digest: self.body.to_vec(),
+            hash: sha3::Keccak256::digest(self.body).into(),
+        })
+    }
+
+    /// See `VAA::verify`.
+    pub fn verify(&self, guardian_set: &GuardianSet) -> Result<VerifiedSignatures, WormholeError> {
+        verify_signatures(
+            self.guardian_set_index,
+            self.signatures(),
+            &signing_hash(self.body),
+            guardian_set,
+        )
+    }
+
+    /// Copy the borrowed fields into an owned `VAA`.
+    pub fn to_vaa(&self) -> VAA {
+        VAA {
+            version: self.version,
+            guardian_set_index: self.guardian_set_index,
+            signatures: self.signatures().copied().collect(),
+            timestamp: self.timestamp,
+            nonce: self.nonce,
+            emitter_chain: self.emitter_chain,
+            emitter_address: *self.emitter_address,
+            sequence: self.sequence,
+            consistency_level: self.consistency_level,
+            payload: self.payload.to_vec(),
+        }
+    }
+}
+
+impl From<VAARef<'_>> for VAA {
+    fn from(other: VAARef<'_>) -> VAA {
+        other.to_vaa()
+    }
+}
+
+/// Builder for constructing VAA's field by field, any field that is not set takes on its default
+/// value. The version defaults to 1, which is the only VAA version `from_bytes` understands.
+///
+/// ```rust
+/// use wormhole_core::{Chain, VAABuilder};
+///
+/// let vaa = VAABuilder::new()
+///     .emitter_chain(Chain::Solana)
+///     .emitter_address([4u8; 32])
+///     .sequence(1)
+///     .payload(b"Hello".to_vec())
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct VAABuilder {
+    vaa: VAA,
+}
+
+impl Default for VAABuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VAABuilder {
+    pub fn new() -> Self {
+        VAABuilder {
+            vaa: VAA {
+                version: 1,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn version(mut self, version: u8) -> Self {
+        self.vaa.version = version;
+        self
+    }
+
+    pub fn guardian_set_index(mut self, guardian_set_index: u32) -> Self {
+        self.vaa.guardian_set_index = guardian_set_index;
+        self
+    }
+
+    /// Append a single signature, signatures are serialized in the order they are added.
+    pub fn signature(mut self, signature: Signature) -> Self {
+        self.vaa.signatures.push(signature);
+        self
+    }
+
+    pub fn signatures(mut self, signatures: Vec<Signature>) -> Self {
+        self.vaa.signatures = signatures;
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: u32) -> Self {
+        self.vaa.timestamp = timestamp;
+        self
+    }
+
+    pub fn nonce(mut self, nonce: u32) -> Self {
+        self.vaa.nonce = nonce;
+        self
+    }
+
+    pub fn emitter_chain(mut self, emitter_chain: Chain) -> Self {
+        self.vaa.emitter_chain = emitter_chain;
+        self
+    }
+
+    pub fn emitter_address(mut self, emitter_address: ForeignAddress) -> Self {
+        self.vaa.emitter_address = emitter_address;
+        self
+    }
+
+    pub fn sequence(mut self, sequence: u64) -> Self {
+        self.vaa.sequence = sequence;
+        self
+    }
+
+    pub fn consistency_level(mut self, consistency_level: u8) -> Self {
+        self.vaa.consistency_level = consistency_level;
+        self
+    }
+
+    pub fn payload(mut self, payload: Vec<u8>) -> Self {
+        self.vaa.payload = payload;
+        self
+    }
+
+    pub fn build(self) -> VAA {
+        self.vaa
+    }
+}
+
+/// Result type for all nom parsers within this library.
+pub type ParseResult<'a, T> = IResult<&'a [u8], T, FieldError<'a>>;
+
+/// The nom error type used by all parsers within this library. Parsers label the field they are
+/// reading using nom's `context`, which allows a failure to be reported with the field name and
+/// its position in the input rather than a bare nom error code.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FieldError<'a> {
+    /// Remaining input at the point of failure.
+    pub input: &'a [u8],
+
+    /// Name of the innermost field that failed, empty if the parser did not label its fields.
+    pub field: &'static str,
+
+    pub reason: ParseErrorReason,
+}
+
+impl<'a> FieldError<'a> {
+    pub fn new(input: &'a [u8], reason: ParseErrorReason) -> Self {
+        FieldError {
+            input,
+            field: "",
+            reason,
+        }
+    }
+
+    /// Convert into a `WormholeError`, where `original` is the input that was passed to the
+    /// outermost parser and is used to compute the byte offset of the failure.
+    pub fn locate(self, original: &[u8]) -> WormholeError {
+        WormholeError::ParseError(ParseError {
+            field: self.field,
+            offset: original.len().saturating_sub(self.input.len()),
+            reason: self.reason,
+        })
+    }
+}
+
+impl<'a> NomParseError<&'a [u8]> for FieldError<'a> {
+    fn from_error_kind(input: &'a [u8], _: ErrorKind) -> Self {
+        FieldError::new(input, ParseErrorReason::InvalidValue)
+    }
+
+    fn append(_: &'a [u8], _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a> ContextError<&'a [u8]> for FieldError<'a> {
+    /// Only the innermost context is kept, so that errors name the most specific field. The input
+    /// is left untouched, so for repeated fields such as signatures the offset points at the
+    /// element that failed rather than the start of the field.
+    fn add_context(_: &'a [u8], field: &'static str, mut other: Self) -> Self {
+        if other.field.is_empty() {
+            other.field = field;
+        }
+        other
+    }
 }
 
 /// Using nom, parse a fixed array of bytes without any allocation. Useful for parsing addresses,
 /// signatures, identifiers, etc.
 #[inline]
-pub fn parse_fixed<const S: usize>(input: &[u8]) -> IResult<&[u8], [u8; S]> {
+pub fn parse_fixed<const S: usize>(input: &[u8]) -> ParseResult<'_, [u8; S]> {
+    let bytes = take_bytes(S)(input)?;
     let mut buffer = [0u8; S];
-    let (i, _) = fill(u8, &mut buffer)(input)?;
-    Ok((i, buffer))
+    buffer.copy_from_slice(bytes.1);
+    Ok((bytes.0, buffer))
+}
+
+/// Take exactly `n` bytes from the input, failing with the number of bytes that were missing.
+#[inline]
+pub fn take_bytes<'a>(n: usize) -> impl Fn(&'a [u8]) -> ParseResult<'a, &'a [u8]> {
+    move |input: &'a [u8]| {
+        if input.len() < n {
+            let reason = ParseErrorReason::Truncated {
+                expected: n,
+                actual: input.len(),
+            };
+            return Err(Err::Error(FieldError::new(input, reason)));
+        }
+        Ok((&input[n..], &input[..n]))
+    }
+}
+
+#[inline]
+pub fn parse_u8(input: &[u8]) -> ParseResult<'_, u8> {
+    let (i, bytes) = parse_fixed::<1>(input)?;
+    Ok((i, bytes[0]))
+}
+
+#[inline]
+pub fn parse_u16(input: &[u8]) -> ParseResult<'_, u16> {
+    let (i, bytes) = parse_fixed(input)?;
+    Ok((i, u16::from_be_bytes(bytes)))
+}
+
+#[inline]
+pub fn parse_u32(input: &[u8]) -> ParseResult<'_, u32> {
+    let (i, bytes) = parse_fixed(input)?;
+    Ok((i, u32::from_be_bytes(bytes)))
+}
+
+#[inline]
+pub fn parse_u64(input: &[u8]) -> ParseResult<'_, u64> {
+    let (i, bytes) = parse_fixed(input)?;
+    Ok((i, u64::from_be_bytes(bytes)))
+}
+
+/// Parse a big-endian 256 bit unsigned integer, used for token amounts and fees.
+#[inline]
+pub fn parse_u256(input: &[u8]) -> ParseResult<'_, U256> {
+    let (i, bytes): (_, [u8; 32]) = parse_fixed(input)?;
+    Ok((i, U256::from_big_endian(&bytes)))
+}
+
+/// Parse a payload ID, failing if it does not match the expected ID.
+#[inline]
+pub fn parse_payload_id<'a>(id: u8) -> impl Fn(&'a [u8]) -> ParseResult<'a, u8> {
+    move |input: &'a [u8]| {
+        let (i, payload_id) = context("payload_id", parse_u8)(input)?;
+        if payload_id != id {
+            return Err(Err::Error(FieldError {
+                input,
+                field: "payload_id",
+                reason: ParseErrorReason::InvalidValue,
+            }));
+        }
+        Ok((i, payload_id))
+    }
 }
 
 /// Parse a Chain ID, which is a 16 bit numeric ID. The mapping of network to ID is defined by the
-/// Wormhole standard.
+/// Wormhole standard, IDs without a known mapping are parsed as `Chain::Unknown`.
 #[inline]
-pub fn parse_chain(input: &[u8]) -> IResult<&[u8], Chain> {
-    let (i, chain) = u16(Endianness::Big)(input)?;
-    let chain = Chain::try_from(chain).map_err(|_| Err::Error(Error::new(i, ErrorKind::NoneOf)))?;
-    Ok((i, chain))
+pub fn parse_chain(input: &[u8]) -> ParseResult<'_, Chain> {
+    let (i, chain) = parse_u16(input)?;
+    Ok((i, Chain::from(chain)))
 }
 
 /// Parse a VAA from a vector of raw bytes. Nom handles situations where the data is either too
-/// short or too long.
+/// short or too long. Only version 1 VAA's are accepted, see `BatchVAA` for version 2.
+#[inline]
+fn parse_vaa(input: &[u8]) -> ParseResult<'_, VAA> {
+    let (i, vaa) = parse_vaa_ref(input)?;
+    Ok((i, vaa.to_vaa()))
+}
+
+/// Parse a VAA without copying any of its fields out of the input.
 #[inline]
-fn parse_vaa(input: &[u8]) -> IResult<&[u8], VAA> {
-    let (i, version) = u8(input)?;
-    let (i, guardian_set_index) = u32(Endianness::Big)(i)?;
-    let (i, signature_count) = u8(i)?;
-    let (i, signatures) = count(parse_fixed, signature_count.into())(i)?;
-    let (i, timestamp) = u32(Endianness::Big)(i)?;
-    let (i, nonce) = u32(Endianness::Big)(i)?;
-    let (i, emitter_chain) = parse_chain(i)?;
-    let (i, emitter_address) = parse_fixed(i)?;
-    let (i, sequence) = u64(Endianness::Big)(i)?;
-    let (i, consistency_level) = u8(i)?;
+fn parse_vaa_ref(input: &[u8]) -> ParseResult<'_, VAARef<'_>> {
+    let (i, version) = context("version", parse_u8)(input)?;
+    if version != 1 {
+        return Err(Err::Error(FieldError {
+            input,
+            field: "version",
+            reason: ParseErrorReason::InvalidValue,
+        }));
+    }
+
+    let (i, guardian_set_index) = context("guardian_set_index", parse_u32)(i)?;
+    let (i, signature_count) = context("signature_count", parse_u8)(i)?;
+
+    // Signatures are taken one at a time so a truncated signature is reported at its own offset.
+    let signatures_start = i;
+    let mut i = i;
+    for _ in 0..signature_count {
+        i = context("signatures", take_bytes(66))(i)?.0;
+    }
+    let signatures = &signatures_start[..66 * signature_count as usize];
+
+    let body = i;
+    let (i, timestamp) = context("timestamp", parse_u32)(i)?;
+    let (i, nonce) = context("nonce", parse_u32)(i)?;
+    let (i, emitter_chain) = context("emitter_chain", parse_chain)(i)?;
+    let (i, emitter_address) = context("emitter_address", take_bytes(32))(i)?;
+    let (i, sequence) = context("sequence", parse_u64)(i)?;
+    let (i, consistency_level) = context("consistency_level", parse_u8)(i)?;
     let (i, payload) = rest(i)?;
     Ok((
         i,
-        VAA {
+        VAARef {
             version,
             guardian_set_index,
             signatures,
+            body,
             timestamp,
             nonce,
             emitter_chain,
-            emitter_address,
+            // Taken as exactly 32 bytes above.
+            emitter_address: <&ForeignAddress>::try_from(emitter_address).unwrap(),
             sequence,
             consistency_level,
-            payload: payload.to_vec(),
+            payload,
         },
     ))
 }
 
 /// All current Wormhole programs using Governance are prefixed with a Governance header with a
 /// consistent format.
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovHeader {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub module: [u8; 32],
     pub action: u8,
     pub chains: Chain,
 }
 
+impl GovHeader {
+    /// Serialize the header into the 35 byte prefix found on all governance payloads.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(35);
+        v.extend_from_slice(&self.module);
+        v.push(self.action);
+        v.extend_from_slice(&u16::from(self.chains).to_be_bytes());
+        v
+    }
+}
+
+/// Governance modules are identified by a name, such as "Core" or "TokenBridge", which is left
+/// zero-padded to 32 bytes on the wire.
+fn pad_module(module: &[u8]) -> [u8; 32] {
+    let mut padded = [0u8; 32];
+    padded[32 - module.len()..].copy_from_slice(module);
+    padded
+}
+
 pub trait GovernanceAction: Sized {
     const ACTION: u8;
     const MODULE: &'static [u8];
 
     /// Implement a nom parser for the Action.
-    fn parse(input: &[u8]) -> IResult<&[u8], Self>;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self>;
 
     /// Serialize to Wormhole wire format.
-    /// fn serialize(&self) -> Result<Vec<u8>, WormholeError>;
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError>;
+
+    /// Serialize an Action into a full governance payload, including the governance header, that
+    /// targets the given chain. This is the inverse of `from_bytes`.
+    fn to_bytes(&self, chain: Chain) -> Result<Vec<u8>, WormholeError> {
+        let header = GovHeader {
+            module: pad_module(Self::MODULE),
+            action: Self::ACTION,
+            chains: chain,
+        };
+
+        let mut v = header.serialize();
+        v.extend_from_slice(&self.serialize()?);
+        Ok(v)
+    }
+
+    /// Wrap the Action into an unsigned VAA from the governance emitter that targets the given
+    /// chain. The returned builder can be used to set the remaining fields, such as the sequence
+    /// and guardian set index, before the VAA is built and signed.
+    fn to_vaa(&self, chain: Chain) -> Result<VAABuilder, WormholeError> {
+        Ok(VAABuilder::new()
+            .emitter_chain(GOVERNANCE_CHAIN)
+            .emitter_address(GOVERNANCE_EMITTER)
+            .consistency_level(32)
+            .payload(self.to_bytes(chain)?))
+    }
 
     /// Parses an Action from a governance payload securely.
     fn from_bytes<T: AsRef<[u8]>>(
         input: T,
         chain: Option<Chain>,
     ) -> Result<(GovHeader, Self), WormholeError> {
-        match parse_action(input.as_ref()).finish() {
+        let input = input.as_ref();
+        match parse_action(input).finish() {
             Ok((_, (header, action))) => {
                 // If no Chain is given, we assume All, which implies always valid.
                 let chain = chain.unwrap_or(Chain::All);
 
                 // Left 0-pad the MODULE in case it is unpadded.
-                let mut module = [0u8; 32];
-                let modlen = Self::MODULE.len();
-                module[32 - modlen..].copy_from_slice(Self::MODULE);
+                let module = pad_module(Self::MODULE);
 
                 // Verify Governance Data.
                 let valid_chain = chain == header.chains || chain == Chain::All;
@@ -213,36 +749,38 @@ pub trait GovernanceAction: Sized {
 
                 Ok((header, action))
             }
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+            Err(e) => Err(e.locate(input)),
         }
     }
 }
 
 #[inline]
-pub fn parse_action<A: GovernanceAction>(input: &[u8]) -> IResult<&[u8], (GovHeader, A)> {
+pub fn parse_action<A: GovernanceAction>(input: &[u8]) -> ParseResult<'_, (GovHeader, A)> {
     let (i, header) = parse_governance_header(input)?;
     let (i, action) = A::parse(i)?;
     Ok((i, (header, action)))
 }
 
 #[inline]
-pub fn parse_governance_header(input: &[u8]) -> IResult<&[u8], GovHeader> {
-    let (i, module) = parse_fixed(input)?;
-    let (i, action) = u8(i)?;
-    let (i, chains) = u16(Endianness::Big)(i)?;
+pub fn parse_governance_header(input: &[u8]) -> ParseResult<'_, GovHeader> {
+    let (i, module) = context("module", parse_fixed)(input)?;
+    let (i, action) = context("action", parse_u8)(i)?;
+    let (i, chains) = context("chain", parse_chain)(i)?;
     Ok((
         i,
         GovHeader {
             module,
             action,
-            chains: Chain::try_from(chains).unwrap(),
+            chains,
         },
     ))
 }
 
 #[cfg(test)]
 mod testing {
-    use super::{parse_governance_header, Chain, VAA};
+    use super::{parse_governance_header, Chain, GovernanceAction, VAABuilder, VAARef, VAA};
+    use crate::vaa::token::GovernanceRegisterChain;
+    use crate::{GuardianSet, ParseError, ParseErrorReason, WormholeError};
 
     #[test]
     fn test_valid_gov_header() {
@@ -264,6 +802,7 @@ mod testing {
     }
 
     // Legacy VAA Signature Struct.
+    #[allow(dead_code)]
     #[derive(Default, Clone)]
     pub struct VAASignature {
         pub signature: Vec<u8>,
@@ -273,7 +812,6 @@ mod testing {
     // Original VAA Parsing Code. Used to compare current code to old for parity.
     pub fn legacy_deserialize(data: &[u8]) -> std::result::Result<VAA, std::io::Error> {
         use byteorder::{BigEndian, ReadBytesExt};
-        use std::convert::TryFrom;
         use std::io::Read;
 
         let mut rdr = std::io::Cursor::new(data);
@@ -293,7 +831,7 @@ mod testing {
         v.signatures = sigs;
         v.timestamp = rdr.read_u32::<BigEndian>()?;
         v.nonce = rdr.read_u32::<BigEndian>()?;
-        v.emitter_chain = Chain::try_from(rdr.read_u16::<BigEndian>()?).unwrap();
+        v.emitter_chain = Chain::from(rdr.read_u16::<BigEndian>()?);
         let mut emitter_address = [0u8; 32];
         rdr.read_exact(&mut emitter_address)?;
         v.emitter_address = emitter_address;
@@ -339,5 +877,195 @@ mod testing {
     }
 
     #[test]
-    fn test_invalid_vaa() {}
+    fn test_invalid_vaa() {
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+
+        // Truncated within the emitter address, which starts after the 6 byte header, a single
+        // signature and the timestamp, nonce and emitter chain.
+        let offset = 6 + 66 + 10;
+        match VAA::from_bytes(&vaa[..offset + 5]) {
+            Err(WormholeError::ParseError(e)) => assert_eq!(
+                e,
+                ParseError {
+                    field: "emitter_address",
+                    offset,
+                    reason: ParseErrorReason::Truncated {
+                        expected: 32,
+                        actual: 5
+                    },
+                }
+            ),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // Claims three signatures, the body is consumed as signatures and the third runs past
+        // the end of the input.
+        let mut invalid = vaa.clone();
+        invalid[5] = 3;
+        match VAA::from_bytes(&invalid) {
+            Err(WormholeError::ParseError(e)) => {
+                assert_eq!(e.field, "signatures");
+                assert_eq!(e.offset, 6 + 2 * 66);
+                assert_eq!(
+                    e.reason,
+                    ParseErrorReason::Truncated {
+                        expected: 66,
+                        actual: vaa.len() - 6 - 2 * 66
+                    }
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // Empty input fails on the very first field.
+        match VAA::from_bytes([]) {
+            Err(WormholeError::ParseError(e)) => {
+                assert_eq!(e.field, "version");
+                assert_eq!(e.offset, 0);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_vaa_round_trip() {
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let parsed = VAA::from_bytes(&vaa).unwrap();
+        assert_eq!(parsed.to_bytes().unwrap(), vaa);
+
+        // The digest is computed over the serialized body, which is everything after the
+        // signatures.
+        let digest = parsed.digest().unwrap();
+        assert_eq!(digest.digest, vaa[6 + 66..]);
+    }
+
+    #[test]
+    fn test_vaa_ref() {
+        let guardian = hex::decode("13947bd48b18e53fdaeee77f3473391ac727c638").unwrap();
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&guardian);
+
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let owned = VAA::from_bytes(&vaa).unwrap();
+        let borrowed = VAARef::from_bytes(&vaa).unwrap();
+
+        // Borrowed fields point into the original buffer.
+        assert_eq!(
+            borrowed.payload().as_ptr(),
+            vaa[vaa.len() - owned.payload.len()..].as_ptr()
+        );
+        assert_eq!(borrowed.body(), &vaa[6 + 66..]);
+        assert_eq!(borrowed.signature_count(), 1);
+        assert_eq!(borrowed.signatures().next(), Some(&owned.signatures[0]));
+        assert_eq!(borrowed.emitter_chain(), owned.emitter_chain);
+        assert_eq!(borrowed.sequence(), owned.sequence);
+
+        assert_eq!(borrowed.to_vaa(), owned);
+        assert_eq!(VAA::from(borrowed), owned);
+        assert_eq!(borrowed.parse_payload(), owned.parse_payload());
+        assert_eq!(
+            borrowed.digest().unwrap().hash,
+            owned.digest().unwrap().hash
+        );
+        assert_eq!(
+            borrowed
+                .verify(&GuardianSet::new(0, vec![address]))
+                .unwrap(),
+            owned.verify(&GuardianSet::new(0, vec![address])).unwrap()
+        );
+
+        // Errors are reported identically to the owned parser.
+        match (
+            VAARef::from_bytes(&vaa[..100]),
+            VAA::from_bytes(&vaa[..100]),
+        ) {
+            (Err(WormholeError::ParseError(a)), Err(WormholeError::ParseError(b))) => {
+                assert_eq!(a, b)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_vaa_builder() {
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let parsed = VAA::from_bytes(&vaa).unwrap();
+
+        let mut emitter_address = [0u8; 32];
+        emitter_address[31] = 4;
+
+        let built = VAABuilder::new()
+            .guardian_set_index(0)
+            .signature(parsed.signatures[0])
+            .timestamp(1)
+            .nonce(1)
+            .emitter_chain(Chain::Solana)
+            .emitter_address(emitter_address)
+            .sequence(20_716_538)
+            .consistency_level(0)
+            .payload(parsed.payload.clone())
+            .build();
+
+        assert_eq!(built, parsed);
+        assert_eq!(built.to_bytes().unwrap(), vaa);
+    }
+
+    #[test]
+    fn test_governance_round_trip() {
+        let payload = hex::decode("000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let (header, action) = GovernanceRegisterChain::from_bytes(&payload, None).unwrap();
+        assert_eq!(action.emitter, Chain::Solana);
+        assert_eq!(action.to_bytes(header.chains).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_serialize_too_many_signatures() {
+        let vaa = VAABuilder::new().signatures(vec![[0u8; 66]; 256]).build();
+        assert!(vaa.to_bytes().is_err());
+    }
+
+    #[test]
+    fn test_verify_vaa() {
+        let guardian = hex::decode("13947bd48b18e53fdaeee77f3473391ac727c638").unwrap();
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&guardian);
+
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let mut vaa = VAA::from_bytes(vaa).unwrap();
+
+        // Signed by the single testnet guardian.
+        let verified = vaa.verify(&GuardianSet::new(0, vec![address])).unwrap();
+        assert_eq!(verified.signers, vec![0]);
+        assert_eq!(verified.quorum, 1);
+        assert!(verified.signed_by(0));
+
+        // Wrong guardian set index.
+        assert!(vaa.verify(&GuardianSet::new(1, vec![address])).is_err());
+
+        // Wrong guardian, and not enough signatures for a larger set.
+        assert!(vaa.verify(&GuardianSet::new(0, vec![[0u8; 20]])).is_err());
+        assert!(vaa.verify(&GuardianSet::new(0, vec![address; 3])).is_err());
+
+        // Tampering with the body invalidates the signature.
+        vaa.sequence += 1;
+        assert!(vaa.verify(&GuardianSet::new(0, vec![address])).is_err());
+    }
+
+    #[test]
+    fn test_guardian_set_quorum() {
+        let quorum = |n| GuardianSet::new(0, vec![[0u8; 20]; n]).quorum();
+        assert_eq!(quorum(1), 1);
+        assert_eq!(quorum(3), 3);
+        assert_eq!(quorum(4), 3);
+        assert_eq!(quorum(19), 13);
+    }
+
+    #[test]
+    fn test_parse_unknown_chain() {
+        for chain in [Chain::Near, Chain::Wormchain, Chain::Unknown(9999)] {
+            let vaa = VAABuilder::new().emitter_chain(chain).build();
+            let parsed = VAA::from_bytes(vaa.to_bytes().unwrap()).unwrap();
+            assert_eq!(parsed.emitter_chain, chain);
+        }
+    }
 }
@@ -0,0 +1,439 @@
+//! Batch VAA's (version 2) carry several observations under a single set of guardian signatures.
+//! Rather than signing each observation body, guardians sign the version byte followed by the list
+//! of observation hashes, so a batch can be verified once and each observation then checked against
+//! its hash.
+//!
+//! Observation hashes are the same double Keccak hash guardians sign for the equivalent version 1
+//! VAA, and the batch is signed using the same double Keccak hash over `version || hashes`, matching
+//! the guardian implementation in `sdk/vaa/structs.go`.
+//!
+//! ```markdown
+//! version            u8 (2)
+//! guardian_set_index u32
+//! signature_count    u8, followed by signatures
+//! hash_count         u8, followed by 32 byte observation hashes
+//! observation_count  u8, equal to hash_count, followed by observations:
+//!     index          u8, position of the observation's hash
+//!     length         u32
+//!     body           same layout as the body of a version 1 VAA
+//! ```
+
+use core::convert::TryFrom;
+use nom::error::context;
+use nom::multi::count;
+use nom::{Err, Finish};
+
+use alloc::vec::Vec;
+
+use crate::vaa::{
+    parse_chain, parse_fixed, parse_u32, parse_u64, parse_u8, signing_hash, take_bytes,
+    verify_signatures, FieldError, ForeignAddress, ParseResult, Signature, VAA,
+};
+use crate::{
+    require, Chain, GuardianSet, ParseErrorReason, VerifiedSignatures,
+    WormholeError::{self, InvalidObservation},
+};
+
+/// Size of an observation body without its payload.
+const OBSERVATION_HEADER_LEN: u32 = 51;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchVAA {
+    pub version: u8,
+    pub guardian_set_index: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_list"))]
+    pub signatures: Vec<Signature>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_list"))]
+    pub hashes: Vec<[u8; 32]>,
+    pub observations: Vec<Observation>,
+}
+
+/// A single message within a batch, this holds the same fields as the body of a version 1 VAA.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observation {
+    /// Position of this observation's hash within `BatchVAA::hashes`.
+    pub index: u8,
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub emitter_chain: Chain,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
+    pub emitter_address: ForeignAddress,
+    pub sequence: u64,
+    pub consistency_level: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
+    pub payload: Vec<u8>,
+}
+
+impl Observation {
+    /// Serialize the observation body, this is identical to the body of the equivalent version 1
+    /// VAA.
+    pub fn serialize_body(&self) -> Vec<u8> {
+        self.to_vaa(0, Vec::new()).serialize_body()
+    }
+
+    /// The double Keccak hash of the observation body, which must match the hash at `index` in the
+    /// batch. This is the hash guardians sign for the equivalent version 1 VAA, not its digest hash.
+    pub fn hash(&self) -> [u8; 32] {
+        signing_hash(&self.serialize_body())
+    }
+
+    fn to_vaa(&self, guardian_set_index: u32, signatures: Vec<Signature>) -> VAA {
+        VAA {
+            version: 1,
+            guardian_set_index,
+            signatures,
+            timestamp: self.timestamp,
+            nonce: self.nonce,
+            emitter_chain: self.emitter_chain,
+            emitter_address: self.emitter_address,
+            sequence: self.sequence,
+            consistency_level: self.consistency_level,
+            payload: self.payload.clone(),
+        }
+    }
+}
+
+impl BatchVAA {
+    /// Given any argument treatable as a series of bytes, attempt to deserialize into a valid
+    /// batch VAA.
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        let input = input.as_ref();
+        match parse_batch_vaa(input).finish() {
+            Ok(input) => Ok(input.1),
+            Err(e) => Err(e.locate(input)),
+        }
+    }
+
+    /// Serialize the batch into the Wormhole wire format, this is the inverse of `from_bytes`.
+    /// This fails if any of the lists are too long to be counted by a single byte, or if an
+    /// observation is too large to be encoded.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WormholeError> {
+        let len = |n: usize| u8::try_from(n).map_err(|_| WormholeError::SerializeFailed);
+
+        let mut v = Vec::new();
+        v.push(self.version);
+        v.extend_from_slice(&self.guardian_set_index.to_be_bytes());
+        v.push(len(self.signatures.len())?);
+        for signature in &self.signatures {
+            v.extend_from_slice(signature);
+        }
+        v.push(len(self.hashes.len())?);
+        for hash in &self.hashes {
+            v.extend_from_slice(hash);
+        }
+        v.push(len(self.observations.len())?);
+        for observation in &self.observations {
+            let body = observation.serialize_body();
+            let body_len = u32::try_from(body.len()).map_err(|_| WormholeError::SerializeFailed)?;
+            v.push(observation.index);
+            v.extend_from_slice(&body_len.to_be_bytes());
+            v.extend_from_slice(&body);
+        }
+        Ok(v)
+    }
+
+    /// The hash of the batch, which is the Keccak hash of the version byte followed by the
+    /// concatenated observation hashes.
+    pub fn hash(&self) -> [u8; 32] {
+        use sha3::Digest;
+        sha3::Keccak256::digest(&self.signing_body()).into()
+    }
+
+    /// As with version 1, guardians sign the hash of the batch hash.
+    pub(crate) fn signing_hash(&self) -> [u8; 32] {
+        signing_hash(&self.signing_body())
+    }
+
+    fn signing_body(&self) -> Vec<u8> {
+        let mut body = Vec::with_capacity(1 + 32 * self.hashes.len());
+        body.push(self.version);
+        for hash in &self.hashes {
+            body.extend_from_slice(hash);
+        }
+        body
+    }
+
+    /// Verify the guardian signatures over the batch, and that every observation matches the hash
+    /// at its index. See `VAA::verify` for the signature rules, which are the same for batches.
+    pub fn verify(&self, guardian_set: &GuardianSet) -> Result<VerifiedSignatures, WormholeError> {
+        let verified = verify_signatures(
+            self.guardian_set_index,
+            self.signatures.iter(),
+            &self.signing_hash(),
+            guardian_set,
+        )?;
+
+        for observation in &self.observations {
+            let hash = self.hashes.get(observation.index as usize);
+            require!(hash == Some(&observation.hash()), InvalidObservation);
+        }
+
+        Ok(verified)
+    }
+
+    /// Extract each observation as a version 1 VAA, in the order they appear in the batch.
+    ///
+    /// The extracted VAA's carry the batch signatures, which sign the batch hash rather than the
+    /// observation and so will not pass `VAA::verify`. Only use these after the batch itself has
+    /// been verified.
+    pub fn to_vaas(&self) -> Vec<VAA> {
+        self.observations
+            .iter()
+            .map(|o| o.to_vaa(self.guardian_set_index, self.signatures.clone()))
+            .collect()
+    }
+}
+
+fn parse_observation(input: &[u8]) -> ParseResult<'_, Observation> {
+    let (i, index) = context("observation_index", parse_u8)(input)?;
+    let (i, len) = context("observation_len", parse_u32)(i)?;
+    if len < OBSERVATION_HEADER_LEN {
+        return Err(Err::Error(FieldError {
+            input: &input[1..],
+            field: "observation_len",
+            reason: ParseErrorReason::InvalidValue,
+        }));
+    }
+
+    let (i, timestamp) = context("timestamp", parse_u32)(i)?;
+    let (i, nonce) = context("nonce", parse_u32)(i)?;
+    let (i, emitter_chain) = context("emitter_chain", parse_chain)(i)?;
+    let (i, emitter_address) = context("emitter_address", parse_fixed)(i)?;
+    let (i, sequence) = context("sequence", parse_u64)(i)?;
+    let (i, consistency_level) = context("consistency_level", parse_u8)(i)?;
+    let payload_len = (len - OBSERVATION_HEADER_LEN) as usize;
+    let (i, payload) = context("payload", take_bytes(payload_len))(i)?;
+    Ok((
+        i,
+        Observation {
+            index,
+            timestamp,
+            nonce,
+            emitter_chain,
+            emitter_address,
+            sequence,
+            consistency_level,
+            payload: payload.to_vec(),
+        },
+    ))
+}
+
+fn parse_batch_vaa(input: &[u8]) -> ParseResult<'_, BatchVAA> {
+    let (i, version) = context("version", parse_u8)(input)?;
+    if version != 2 {
+        return Err(Err::Error(FieldError {
+            input,
+            field: "version",
+            reason: ParseErrorReason::InvalidValue,
+        }));
+    }
+
+    let (i, guardian_set_index) = context("guardian_set_index", parse_u32)(i)?;
+    let (i, signature_count) = context("signature_count", parse_u8)(i)?;
+    let (i, signatures) = context("signatures", count(parse_fixed, signature_count.into()))(i)?;
+    let (i, hash_count) = context("hash_count", parse_u8)(i)?;
+    let (i, hashes) = context("hashes", count(parse_fixed, hash_count.into()))(i)?;
+    let (rest, observation_count) = context("observation_count", parse_u8)(i)?;
+
+    // Every hash belongs to exactly one observation.
+    if observation_count != hash_count {
+        return Err(Err::Error(FieldError {
+            input: i,
+            field: "observation_count",
+            reason: ParseErrorReason::InvalidValue,
+        }));
+    }
+    let i = rest;
+
+    let (i, observations) = count(parse_observation, observation_count.into())(i)?;
+
+    // Unlike version 1 there is no trailing payload, so anything left over is malformed.
+    if !i.is_empty() {
+        return Err(Err::Error(FieldError {
+            input: i,
+            field: "observations",
+            reason: ParseErrorReason::InvalidValue,
+        }));
+    }
+
+    Ok((
+        i,
+        BatchVAA {
+            version,
+            guardian_set_index,
+            signatures,
+            hashes,
+            observations,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{BatchVAA, Observation};
+    use crate::{Chain, GuardianSet, ParseErrorReason, WormholeError, VAA};
+
+    fn observation(index: u8, sequence: u64) -> Observation {
+        Observation {
+            index,
+            timestamp: 1,
+            nonce: 2,
+            emitter_chain: Chain::Ethereum,
+            emitter_address: [4u8; 32],
+            sequence,
+            consistency_level: 15,
+            payload: vec![index; 10],
+        }
+    }
+
+    fn batch() -> BatchVAA {
+        let observations = vec![observation(0, 100), observation(1, 101)];
+        BatchVAA {
+            version: 2,
+            guardian_set_index: 3,
+            signatures: vec![[7u8; 66]],
+            hashes: observations.iter().map(Observation::hash).collect(),
+            observations,
+        }
+    }
+
+    #[test]
+    fn test_batch_round_trip() {
+        let batch = batch();
+        let bytes = batch.to_bytes().unwrap();
+        assert_eq!(bytes[0], 2);
+        assert_eq!(BatchVAA::from_bytes(&bytes).unwrap(), batch);
+
+        // Trailing bytes are rejected.
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(BatchVAA::from_bytes(&trailing).is_err());
+
+        // Version 1 VAA's are not batches, and batches are not version 1 VAA's.
+        match VAA::from_bytes(&bytes) {
+            Err(WormholeError::ParseError(e)) => {
+                assert_eq!(e.field, "version");
+                assert_eq!(e.reason, ParseErrorReason::InvalidValue);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let mut v1 = bytes;
+        v1[0] = 1;
+        assert!(BatchVAA::from_bytes(&v1).is_err());
+    }
+
+    #[test]
+    fn test_batch_observations() {
+        let batch = batch();
+        let vaas = batch.to_vaas();
+        assert_eq!(vaas.len(), 2);
+
+        // Each extracted VAA is signed over the observation hash in the batch.
+        for (vaa, hash) in vaas.iter().zip(&batch.hashes) {
+            assert_eq!(vaa.version, 1);
+            assert_eq!(vaa.guardian_set_index, 3);
+            assert_eq!(&vaa.signing_hash(), hash);
+        }
+        assert_eq!(vaas[1].sequence, 101);
+        assert_eq!(vaas[1].payload, vec![1u8; 10]);
+    }
+
+    #[test]
+    fn test_truncated_observation() {
+        let bytes = batch().to_bytes().unwrap();
+        match BatchVAA::from_bytes(&bytes[..bytes.len() - 1]) {
+            Err(WormholeError::ParseError(e)) => {
+                assert_eq!(e.field, "payload");
+                assert_eq!(
+                    e.reason,
+                    ParseErrorReason::Truncated {
+                        expected: 10,
+                        actual: 9
+                    }
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    /// Batch of two observations signed by the guardians with secret keys 1, 2 and 3, as produced
+    /// by the guardian implementation (`TestBatchVAAFixture` in sdk/vaa/structs_test.go).
+    const GO_BATCH: &str = "02000000000300b4d299eb40c9d969ccf44bad83ff4c9c4b76f57868c5364b2a6d85f30beceaae519f184e36d0795195543fa191c79c5cb088bdd9d39712af4b44110a7fdf39d80101dd13a34001541c1dee784d4f26ca1ecb7b5506a044b6923d897cb3ba893db4a443a1dad50ccd41be10b482c50285d42ad8fdcae33f939352931a25cadc0637c4010229542af341f99a26c446dc6071a8b21801a8a06eeaedc654fc5c28cee4093ba73e1ff93ef54bab3bd81b11d23cc1544fc04c58db415fd4554abcbae0189969b20102b6862eca56ec6cac61caacc515f11ef2ea016dfd8bc7f1cd4a98fd7324641e904d29d79ccd7012f3ff1b4269dfb92174a9b1709381cce58c6b37f99d4337686f02000000004462b48e000000000700020000000000000000000000000000000000000000000000000000000000000004000000000000000a0f6669727374206f62736572766174696f6e010000004562b48e000000000700020000000000000000000000000000000000000000000000000000000000000004000000000000000b0f7365636f6e64206f62736572766174696f6e";
+
+    #[test]
+    fn test_go_batch_fixture() {
+        let bytes = hex::decode(GO_BATCH).unwrap();
+        let batch = BatchVAA::from_bytes(&bytes).unwrap();
+        assert_eq!(batch.to_bytes().unwrap(), bytes);
+        assert_eq!(batch.observations.len(), 2);
+        assert_eq!(batch.observations[1].sequence, 11);
+        assert_eq!(
+            batch.observations[1].payload,
+            b"second observation".to_vec()
+        );
+
+        // Observation hashes are the signing hash of the equivalent version 1 VAA.
+        assert_eq!(
+            hex::encode(batch.hashes[0]),
+            "b6862eca56ec6cac61caacc515f11ef2ea016dfd8bc7f1cd4a98fd7324641e90"
+        );
+        assert_eq!(batch.observations[0].hash(), batch.hashes[0]);
+        assert_eq!(
+            hex::encode(batch.signing_hash()),
+            "5c2ebea8db88248412332355795cf0e33249a163dd5e0fd6bbfc84fbda44ec63"
+        );
+
+        let guardians = [
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf",
+            "2b5ad5c4795c026514f8317c7a215e218dccd6cf",
+            "6813eb9362372eef6200f3b1dbc3f819671cba69",
+        ];
+        let guardians = guardians
+            .iter()
+            .map(|g| {
+                let mut address = [0u8; 20];
+                address.copy_from_slice(&hex::decode(g).unwrap());
+                address
+            })
+            .collect();
+        let verified = batch.verify(&GuardianSet::new(0, guardians)).unwrap();
+        assert_eq!(verified.signers, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_hash_count_mismatch() {
+        let mut batch = batch();
+        batch.hashes.truncate(1);
+        let bytes = batch.to_bytes().unwrap();
+        match BatchVAA::from_bytes(&bytes) {
+            Err(WormholeError::ParseError(e)) => {
+                assert_eq!(e.field, "observation_count");
+                assert_eq!(e.reason, ParseErrorReason::InvalidValue);
+                assert_eq!(e.offset, 1 + 4 + 1 + 66 + 1 + 32);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_batch_verify() {
+        use crate::signing::{guardian_set, sign_batch};
+
+        let keys = [[1u8; 32], [2u8; 32], [3u8; 32]];
+        let set = guardian_set(3, &keys).unwrap();
+
+        let mut batch = sign_batch(batch(), &keys).unwrap();
+        let verified = batch.verify(&set).unwrap();
+        assert_eq!(verified.signers, vec![0, 1, 2]);
+
+        // An observation that does not match its hash fails, even with valid signatures.
+        batch.observations[1].sequence += 1;
+        assert!(matches!(
+            batch.verify(&set),
+            Err(WormholeError::InvalidObservation)
+        ));
+    }
+}
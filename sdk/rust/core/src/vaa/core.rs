@@ -5,39 +5,69 @@
 //! The core bridge does not define any general VAA's, thus all the payloads in this file are
 //! expected to require governance to be executed.
 
-use nom::multi::{count, fill};
-use nom::number::complete::{u32, u8};
-use nom::number::Endianness;
-use nom::IResult;
+use core::convert::TryFrom;
+use nom::error::context;
+use nom::multi::count;
 use primitive_types::U256;
 
-use crate::vaa::{parse_fixed, GovernanceAction};
+use alloc::vec::Vec;
 
+use crate::vaa::{parse_fixed, parse_u256, parse_u32, parse_u8, GovernanceAction, ParseResult};
+use crate::WormholeError;
+
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceContractUpgrade {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub new_contract: [u8; 32],
 }
 
+impl GovernanceContractUpgrade {
+    pub fn new(new_contract: [u8; 32]) -> Self {
+        GovernanceContractUpgrade { new_contract }
+    }
+}
+
 impl GovernanceAction for GovernanceContractUpgrade {
     const MODULE: &'static [u8] = b"Core";
     const ACTION: u8 = 1;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, new_contract) = parse_fixed(input)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, new_contract) = context("new_contract", parse_fixed)(input)?;
         Ok((i, Self { new_contract }))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        Ok(self.new_contract.to_vec())
+    }
 }
 
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceGuardianSetChange {
     pub new_guardian_set_index: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_list"))]
     pub new_guardian_set: Vec<[u8; 20]>,
 }
 
+impl GovernanceGuardianSetChange {
+    pub fn new(new_guardian_set_index: u32, new_guardian_set: Vec<[u8; 20]>) -> Self {
+        GovernanceGuardianSetChange {
+            new_guardian_set_index,
+            new_guardian_set,
+        }
+    }
+}
+
 impl GovernanceAction for GovernanceGuardianSetChange {
     const MODULE: &'static [u8] = b"Core";
     const ACTION: u8 = 2;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, new_guardian_set_index) = u32(Endianness::Big)(input)?;
-        let (i, guardian_count) = u8(i)?;
-        let (i, new_guardian_set) = count(parse_fixed, guardian_count.into())(i)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, new_guardian_set_index) = context("new_guardian_set_index", parse_u32)(input)?;
+        let (i, guardian_count) = context("guardian_count", parse_u8)(i)?;
+        let (i, new_guardian_set) = context(
+            "new_guardian_set",
+            count(parse_fixed, guardian_count.into()),
+        )(i)?;
         Ok((
             i,
             Self {
@@ -46,45 +76,163 @@ impl GovernanceAction for GovernanceGuardianSetChange {
             },
         ))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let guardian_count = u8::try_from(self.new_guardian_set.len())
+            .map_err(|_| WormholeError::SerializeFailed)?;
+
+        let mut v = Vec::with_capacity(5 + 20 * self.new_guardian_set.len());
+        v.extend_from_slice(&self.new_guardian_set_index.to_be_bytes());
+        v.push(guardian_count);
+        for guardian in &self.new_guardian_set {
+            v.extend_from_slice(guardian);
+        }
+        Ok(v)
+    }
 }
 
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceSetMessageFee {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::u256_dec"))]
     pub fee: U256,
 }
 
+impl GovernanceSetMessageFee {
+    pub fn new(fee: impl Into<U256>) -> Self {
+        GovernanceSetMessageFee { fee: fee.into() }
+    }
+}
+
 impl GovernanceAction for GovernanceSetMessageFee {
     const MODULE: &'static [u8] = b"Core";
     const ACTION: u8 = 3;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, fee) = context("fee", parse_u256)(input)?;
+        Ok((i, Self { fee }))
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
         let mut fee = [0u8; 32];
-        let (i, _) = fill(u8, &mut fee)(input)?;
-        Ok((
-            i,
-            Self {
-                fee: U256::from_big_endian(&fee),
-            },
-        ))
+        self.fee.to_big_endian(&mut fee);
+        Ok(fee.to_vec())
     }
 }
 
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceTransferFees {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::u256_dec"))]
     pub amount: U256,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub to: [u8; 32],
 }
 
+impl GovernanceTransferFees {
+    pub fn new(amount: impl Into<U256>, to: [u8; 32]) -> Self {
+        GovernanceTransferFees {
+            amount: amount.into(),
+            to,
+        }
+    }
+}
+
 impl GovernanceAction for GovernanceTransferFees {
     const MODULE: &'static [u8] = b"Core";
     const ACTION: u8 = 4;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, amount) = context("amount", parse_u256)(input)?;
+        let (i, to) = context("to", parse_fixed)(i)?;
+        Ok((i, Self { amount, to }))
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
         let mut amount = [0u8; 32];
-        let (i, _) = fill(u8, &mut amount)(input)?;
-        let (i, to) = parse_fixed(i)?;
-        Ok((
-            i,
-            Self {
-                amount: U256::from_big_endian(&amount),
-                to,
-            },
-        ))
+        self.amount.to_big_endian(&mut amount);
+
+        let mut v = Vec::with_capacity(64);
+        v.extend_from_slice(&amount);
+        v.extend_from_slice(&self.to);
+        Ok(v)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{
+        GovernanceContractUpgrade, GovernanceGuardianSetChange, GovernanceSetMessageFee,
+        GovernanceTransferFees,
+    };
+    use crate::vaa::{GovernanceAction, GOVERNANCE_CHAIN, GOVERNANCE_EMITTER};
+    use crate::{Chain, Payload, VAA};
+    use primitive_types::U256;
+
+    #[test]
+    fn test_guardian_set_change_round_trip() {
+        let action = GovernanceGuardianSetChange {
+            new_guardian_set_index: 1,
+            new_guardian_set: vec![[0xbe; 20], [0xef; 20]],
+        };
+
+        let payload = action.to_bytes(Chain::All).unwrap();
+        let (header, parsed) = GovernanceGuardianSetChange::from_bytes(&payload, None).unwrap();
+        assert_eq!(header.action, 2);
+        assert_eq!(parsed.new_guardian_set_index, 1);
+        assert_eq!(parsed.new_guardian_set, action.new_guardian_set);
+    }
+
+    #[test]
+    fn test_fee_actions_round_trip() {
+        let action = GovernanceSetMessageFee {
+            fee: U256::from(1000),
+        };
+        let payload = action.to_bytes(Chain::Solana).unwrap();
+        let (_, parsed) =
+            GovernanceSetMessageFee::from_bytes(&payload, Some(Chain::Solana)).unwrap();
+        assert_eq!(parsed.fee, action.fee);
+
+        let action = GovernanceTransferFees {
+            amount: U256::from(1000),
+            to: [7u8; 32],
+        };
+        let payload = action.to_bytes(Chain::Solana).unwrap();
+        let (_, parsed) =
+            GovernanceTransferFees::from_bytes(&payload, Some(Chain::Solana)).unwrap();
+        assert_eq!(parsed.amount, action.amount);
+        assert_eq!(parsed.to, action.to);
+    }
+
+    #[test]
+    fn test_governance_vaa() {
+        // Upgrade the contract on Solana, drafted against the devnet guardian set.
+        let vaa = GovernanceContractUpgrade::new([9u8; 32])
+            .to_vaa(Chain::Solana)
+            .unwrap()
+            .guardian_set_index(0)
+            .sequence(1)
+            .build();
+
+        let vaa = VAA::from_bytes(vaa.to_bytes().unwrap()).unwrap();
+        assert_eq!(vaa.emitter_chain, GOVERNANCE_CHAIN);
+        assert_eq!(vaa.emitter_address, GOVERNANCE_EMITTER);
+        match vaa.parse_payload() {
+            Payload::CoreContractUpgrade(header, action) => {
+                assert_eq!(header.chains, Chain::Solana);
+                assert_eq!(action, GovernanceContractUpgrade::new([9u8; 32]));
+            }
+            other => panic!("unexpected payload: {:?}", other),
+        }
+
+        // The payload is rejected by contracts on other chains.
+        assert!(
+            GovernanceContractUpgrade::from_bytes(&vaa.payload, Some(Chain::Ethereum)).is_err()
+        );
+
+        let vaa = GovernanceSetMessageFee::new(100)
+            .to_vaa(Chain::All)
+            .unwrap()
+            .build();
+        let (_, action) = GovernanceSetMessageFee::from_bytes(&vaa.payload, None).unwrap();
+        assert_eq!(action.fee, U256::from(100));
     }
 }
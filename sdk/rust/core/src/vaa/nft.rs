@@ -3,23 +3,30 @@
 //! parsers can be used to read these VAAs. It also defines the Governance actions that this module
 //! supports, namely contract upgrades and chain registrations.
 
-use nom::bytes::complete::take;
-use nom::combinator::verify;
-use nom::number::complete::u8;
-use nom::{Finish, IResult};
+use core::convert::TryFrom;
+use core::str::from_utf8;
+use nom::error::context;
+use nom::{Err, Finish};
 use primitive_types::U256;
-use std::str::from_utf8;
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
 
 use crate::vaa::ShortUTFString;
-use crate::vaa::{parse_chain, parse_fixed, GovernanceAction};
-use crate::{parse_fixed_utf8, Chain, WormholeError};
+use crate::vaa::{
+    parse_chain, parse_fixed, parse_payload_id, parse_u256, parse_u8, take_bytes, FieldError,
+    GovernanceAction, ParseResult,
+};
+use crate::{parse_fixed_utf8, serialize_fixed_utf8, Chain, ParseErrorReason, WormholeError};
 
 /// Transfer is a message containing specifics detailing a token lock up on a sending chain. Chains
 /// that are attempting to initiate a transfer must lock up tokens in some manner, such as in a
 /// custody account or via burning, before emitting this message.
 #[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transfer {
     /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub nft_address: [u8; 32],
 
     /// Chain ID of the token
@@ -32,12 +39,14 @@ pub struct Transfer {
     pub name: ShortUTFString,
 
     /// TokenID of the token (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::u256_dec"))]
     pub token_id: U256,
 
     /// URI of the token metadata
     pub uri: ShortUTFString,
 
     /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub to: [u8; 32],
 
     /// Chain ID of the recipient
@@ -46,31 +55,64 @@ pub struct Transfer {
 
 impl Transfer {
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
-        match parse_payload_transfer(input.as_ref()).finish() {
+        let input = input.as_ref();
+        match parse_payload_transfer(input).finish() {
             Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+            Err(e) => Err(e.locate(input)),
         }
     }
+
+    /// Serialize to Wormhole wire format, including the payload ID. Symbol and name are truncated
+    /// to 32 bytes, the URI is length prefixed and so cannot be longer than 255 bytes.
+    pub fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let uri_len = u8::try_from(self.uri.len()).map_err(|_| WormholeError::SerializeFailed)?;
+
+        let mut token_id = [0u8; 32];
+        self.token_id.to_big_endian(&mut token_id);
+
+        let mut v = Vec::with_capacity(166 + self.uri.len());
+        v.push(0x1);
+        v.extend_from_slice(&self.nft_address);
+        v.extend_from_slice(&u16::from(self.nft_chain).to_be_bytes());
+        v.extend_from_slice(&serialize_fixed_utf8::<32>(&self.symbol));
+        v.extend_from_slice(&serialize_fixed_utf8::<32>(&self.name));
+        v.extend_from_slice(&token_id);
+        v.push(uri_len);
+        v.extend_from_slice(self.uri.as_bytes());
+        v.extend_from_slice(&self.to);
+        v.extend_from_slice(&u16::from(self.to_chain).to_be_bytes());
+        Ok(v)
+    }
 }
 
-fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
+pub(crate) fn parse_payload_transfer(input: &[u8]) -> ParseResult<'_, Transfer> {
     // Parse Payload
-    let (i, _) = verify(u8, |&s| s == 0x1)(input)?;
-    let (i, nft_address) = parse_fixed(i)?;
-    let (i, nft_chain) = parse_chain(i)?;
-    let (i, symbol): (_, [u8; 32]) = parse_fixed(i)?;
-    let (i, name): (_, [u8; 32]) = parse_fixed(i)?;
-    let (i, token_id): (_, [u8; 32]) = parse_fixed(i)?;
-    let (i, uri_len) = u8(i)?;
-    let (i, uri) = take(uri_len)(i)?;
-    let (i, to) = parse_fixed(i)?;
-    let (i, to_chain) = parse_chain(i)?;
+    let (i, _) = parse_payload_id(0x1)(input)?;
+    let (i, nft_address) = context("nft_address", parse_fixed)(i)?;
+    let (i, nft_chain) = context("nft_chain", parse_chain)(i)?;
+    let (i, symbol): (_, [u8; 32]) = context("symbol", parse_fixed)(i)?;
+    let (i, name): (_, [u8; 32]) = context("name", parse_fixed)(i)?;
+    let (i, token_id) = context("token_id", parse_u256)(i)?;
+    let (i, uri_len) = context("uri_len", parse_u8)(i)?;
+    let uri_start = i;
+    let (i, uri) = context("uri", take_bytes(uri_len.into()))(i)?;
+    let (i, to) = context("to", parse_fixed)(i)?;
+    let (i, to_chain) = context("to_chain", parse_chain)(i)?;
 
     // Name/Symbol and URI should be UTF-8 strings, attempt to parse the first two by removing
     // invalid bytes -- for the latter, assume UTF-8 and fail if unparseable.
-    let name = parse_fixed_utf8::<_, 32>(name).unwrap();
-    let symbol = parse_fixed_utf8::<_, 32>(symbol).unwrap();
-    let uri = from_utf8(uri).unwrap().to_string();
+    let name = parse_fixed_utf8(&name);
+    let symbol = parse_fixed_utf8(&symbol);
+    let uri = match from_utf8(uri) {
+        Ok(uri) => uri.to_string(),
+        Err(_) => {
+            return Err(Err::Error(FieldError {
+                input: uri_start,
+                field: "uri",
+                reason: ParseErrorReason::InvalidUtf8,
+            }))
+        }
+    };
 
     Ok((
         i,
@@ -79,7 +121,7 @@ fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
             nft_chain,
             symbol,
             name,
-            token_id: U256::from_big_endian(&token_id),
+            token_id,
             uri,
             to,
             to_chain,
@@ -88,17 +130,19 @@ fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
 }
 
 #[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceRegisterChain {
     pub emitter: Chain,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub endpoint_address: [u8; 32],
 }
 
 impl GovernanceAction for GovernanceRegisterChain {
     const MODULE: &'static [u8] = b"NFTBridge";
     const ACTION: u8 = 1;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, emitter) = parse_chain(input)?;
-        let (i, endpoint_address) = parse_fixed(i)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, emitter) = context("emitter", parse_chain)(input)?;
+        let (i, endpoint_address) = context("endpoint_address", parse_fixed)(i)?;
         Ok((
             i,
             Self {
@@ -107,18 +151,111 @@ impl GovernanceAction for GovernanceRegisterChain {
             },
         ))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut v = Vec::with_capacity(34);
+        v.extend_from_slice(&u16::from(self.emitter).to_be_bytes());
+        v.extend_from_slice(&self.endpoint_address);
+        Ok(v)
+    }
 }
 
 #[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceContractUpgrade {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub new_contract: [u8; 32],
 }
 
 impl GovernanceAction for GovernanceContractUpgrade {
     const MODULE: &'static [u8] = b"NFTBridge";
     const ACTION: u8 = 2;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, new_contract) = parse_fixed(input)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, new_contract) = context("new_contract", parse_fixed)(input)?;
         Ok((i, Self { new_contract }))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        Ok(self.new_contract.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::Transfer;
+    use crate::{Chain, ParseErrorReason, WormholeError};
+    use primitive_types::U256;
+
+    #[test]
+    fn test_transfer_round_trip() {
+        let payload = hex::decode("01000000000000000000000000bc4ca0eda7647a8ab7c2061c2e118a18a936f13d00024241594300000000000000000000000000000000000000000000000000000000426f7265644170655961636874436c7562000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a1e68747470733a2f2f6578616d706c652e636f6d2f6e66742f312e6a736f6e000000000000000000000000f7f7dde848e7450a029cd0a9bd9bdae4b5147db30001").unwrap();
+        let transfer = Transfer::from_bytes(&payload).unwrap();
+
+        assert_eq!(transfer.nft_chain, Chain::Ethereum);
+        assert_eq!(transfer.symbol, "BAYC");
+        assert_eq!(transfer.name, "BoredApeYachtClub");
+        assert_eq!(transfer.token_id, U256::from(42));
+        assert_eq!(transfer.uri, "https://example.com/nft/1.json");
+        assert_eq!(transfer.to_chain, Chain::Solana);
+        assert_eq!(transfer.serialize().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_invalid_uri() {
+        let transfer = Transfer {
+            nft_address: [0u8; 32],
+            nft_chain: Chain::Ethereum,
+            symbol: String::new(),
+            name: String::new(),
+            token_id: U256::zero(),
+            uri: "uri".to_string(),
+            to: [0u8; 32],
+            to_chain: Chain::Solana,
+        };
+
+        // Replace the URI with invalid UTF-8, this used to panic.
+        let mut payload = transfer.serialize().unwrap();
+        let uri_offset = 1 + 32 + 2 + 32 + 32 + 32 + 1;
+        payload[uri_offset] = 0xff;
+        match Transfer::from_bytes(&payload) {
+            Err(WormholeError::ParseError(e)) => {
+                assert_eq!(e.field, "uri");
+                assert_eq!(e.offset, uri_offset);
+                assert_eq!(e.reason, ParseErrorReason::InvalidUtf8);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // A URI length that runs past the end of the payload.
+        let mut payload = transfer.serialize().unwrap();
+        payload[uri_offset - 1] = 0xff;
+        match Transfer::from_bytes(&payload) {
+            Err(WormholeError::ParseError(e)) => {
+                assert_eq!(e.field, "uri");
+                assert_eq!(
+                    e.reason,
+                    ParseErrorReason::Truncated {
+                        expected: 255,
+                        actual: 3 + 32 + 2
+                    }
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_serialize_uri_too_long() {
+        let transfer = Transfer {
+            nft_address: [0u8; 32],
+            nft_chain: Chain::Ethereum,
+            symbol: String::new(),
+            name: String::new(),
+            token_id: U256::zero(),
+            uri: "a".repeat(256),
+            to: [0u8; 32],
+            to_chain: Chain::Solana,
+        };
+        assert!(transfer.serialize().is_err());
+    }
 }
@@ -0,0 +1,160 @@
+//! This module identifies which Wormhole standard a VAA payload follows and parses it into the
+//! matching type. Payloads are identified by their leading payload ID, or for governance payloads
+//! by the module and action found in the governance header.
+//!
+//! Payloads are not tagged with the bridge that emitted them, so a payload is only identified as a
+//! type when it parses completely with no trailing bytes. This is how token and NFT transfers,
+//! which share payload ID 1, are told apart. Anything else is returned as `Payload::Raw`.
+
+use alloc::vec::Vec;
+
+use crate::vaa::{
+    core, nft, pad_module, parse_governance_header, token, GovHeader, GovernanceAction, ParseResult,
+};
+
+/// Any payload known to this library, governance payloads carry the parsed governance header so
+/// that callers can check the targeted chain.
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Payload {
+    TokenTransfer(token::Transfer),
+    TokenAssetMeta(token::AssetMeta),
+    TokenTransferWithPayload(token::TransferWithPayload),
+    NftTransfer(nft::Transfer),
+    CoreContractUpgrade(GovHeader, core::GovernanceContractUpgrade),
+    CoreGuardianSetChange(GovHeader, core::GovernanceGuardianSetChange),
+    CoreSetMessageFee(GovHeader, core::GovernanceSetMessageFee),
+    CoreTransferFees(GovHeader, core::GovernanceTransferFees),
+    TokenRegisterChain(GovHeader, token::GovernanceRegisterChain),
+    TokenContractUpgrade(GovHeader, token::GovernanceContractUpgrade),
+    NftRegisterChain(GovHeader, nft::GovernanceRegisterChain),
+    NftContractUpgrade(GovHeader, nft::GovernanceContractUpgrade),
+    Raw(#[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))] Vec<u8>),
+}
+
+impl Payload {
+    /// Identify and parse a payload. This never fails, payloads that cannot be identified are
+    /// returned as `Payload::Raw`.
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Payload {
+        let input = input.as_ref();
+        parse_governance(input)
+            .or_else(|| parse_message(input))
+            .unwrap_or_else(|| Payload::Raw(input.to_vec()))
+    }
+}
+
+/// Only accept a parse result that consumed the entire input.
+fn complete<T>(result: ParseResult<'_, T>) -> Option<T> {
+    match result {
+        Ok(([], value)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Parse the body of a governance action if the header matches the action's module and action.
+fn action<A: GovernanceAction>(header: &GovHeader, input: &[u8]) -> Option<A> {
+    if header.module != pad_module(A::MODULE) || header.action != A::ACTION {
+        return None;
+    }
+    complete(A::parse(input))
+}
+
+fn parse_governance(input: &[u8]) -> Option<Payload> {
+    let (i, header) = parse_governance_header(input).ok()?;
+    let h = &header;
+    action(h, i)
+        .map(|a| Payload::CoreContractUpgrade(h.clone(), a))
+        .or_else(|| action(h, i).map(|a| Payload::CoreGuardianSetChange(h.clone(), a)))
+        .or_else(|| action(h, i).map(|a| Payload::CoreSetMessageFee(h.clone(), a)))
+        .or_else(|| action(h, i).map(|a| Payload::CoreTransferFees(h.clone(), a)))
+        .or_else(|| action(h, i).map(|a| Payload::TokenRegisterChain(h.clone(), a)))
+        .or_else(|| action(h, i).map(|a| Payload::TokenContractUpgrade(h.clone(), a)))
+        .or_else(|| action(h, i).map(|a| Payload::NftRegisterChain(h.clone(), a)))
+        .or_else(|| action(h, i).map(|a| Payload::NftContractUpgrade(h.clone(), a)))
+}
+
+fn parse_message(input: &[u8]) -> Option<Payload> {
+    match input.first()? {
+        0x1 => complete(token::parse_payload_transfer(input))
+            .map(Payload::TokenTransfer)
+            .or_else(|| complete(nft::parse_payload_transfer(input)).map(Payload::NftTransfer)),
+        0x2 => complete(token::parse_payload_asset_meta(input)).map(Payload::TokenAssetMeta),
+        0x3 => complete(token::parse_payload_transfer_with_payload(input))
+            .map(Payload::TokenTransferWithPayload),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::Payload;
+    use crate::vaa::{core, token, GovernanceAction};
+    use crate::{Chain, VAA};
+
+    #[test]
+    fn test_parse_governance_payload() {
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let vaa = VAA::from_bytes(vaa).unwrap();
+
+        match vaa.parse_payload() {
+            Payload::TokenRegisterChain(header, action) => {
+                assert_eq!(header.chains, Chain::All);
+                assert_eq!(action.emitter, Chain::Solana);
+            }
+            other => panic!("unexpected payload: {:?}", other),
+        }
+
+        // Same layout, but a different module, is a different action entirely.
+        let upgrade = core::GovernanceContractUpgrade {
+            new_contract: [1u8; 32],
+        };
+        let payload = upgrade.to_bytes(Chain::Solana).unwrap();
+        assert!(matches!(
+            Payload::from_bytes(payload),
+            Payload::CoreContractUpgrade(_, core::GovernanceContractUpgrade { new_contract })
+                if new_contract == [1u8; 32]
+        ));
+    }
+
+    #[test]
+    fn test_parse_message_payload() {
+        let transfer = hex::decode("010000000000000000000000000000000000000000000000000000000005f5e10001000000000000000000000000000000000000000000000000000000757573640003000000000000000000000000f7f7dde848e7450a029cd0a9bd9bdae4b5147db3000300000000000000000000000000000000000000000000000000000000000f4240").unwrap();
+        assert!(matches!(
+            Payload::from_bytes(&transfer),
+            Payload::TokenTransfer(token::Transfer {
+                to_chain: Chain::Terra,
+                ..
+            })
+        ));
+
+        let nft = hex::decode("01000000000000000000000000bc4ca0eda7647a8ab7c2061c2e118a18a936f13d00024241594300000000000000000000000000000000000000000000000000000000426f7265644170655961636874436c7562000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a1e68747470733a2f2f6578616d706c652e636f6d2f6e66742f312e6a736f6e000000000000000000000000f7f7dde848e7450a029cd0a9bd9bdae4b5147db30001").unwrap();
+        assert!(matches!(Payload::from_bytes(&nft), Payload::NftTransfer(_)));
+
+        let meta = hex::decode("02000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200021257455448000000000000000000000000000000000000000000000000000000005772617070656420457468657200000000000000000000000000000000000000").unwrap();
+        assert!(matches!(
+            Payload::from_bytes(&meta),
+            Payload::TokenAssetMeta(_)
+        ));
+
+        let with_payload = token::TransferWithPayload {
+            amount: 1.into(),
+            token_address: [0u8; 32],
+            token_chain: Chain::Solana,
+            to: [0u8; 32],
+            to_chain: Chain::Near,
+            from_address: [0u8; 32],
+            payload: vec![1, 2, 3],
+        };
+        assert_eq!(
+            Payload::from_bytes(with_payload.serialize().unwrap()),
+            Payload::TokenTransferWithPayload(with_payload)
+        );
+
+        // Trailing bytes, unknown payload IDs and empty payloads are all kept raw.
+        let mut trailing = transfer.clone();
+        trailing.push(0);
+        assert_eq!(Payload::from_bytes(&trailing), Payload::Raw(trailing));
+        assert_eq!(Payload::from_bytes([0x9]), Payload::Raw(vec![0x9]));
+        assert_eq!(Payload::from_bytes([]), Payload::Raw(vec![]));
+    }
+}
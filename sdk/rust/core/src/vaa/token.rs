@@ -3,78 +3,191 @@
 //! parsers can be used to read these VAAs. It also defines the Governance actions that this module
 //! supports, namely contract upgrades and chain registrations.
 
-use nom::combinator::verify;
-use nom::multi::fill;
-use nom::number::complete::u8;
-use nom::{Finish, IResult};
+use nom::combinator::rest;
+use nom::error::context;
+use nom::Finish;
 use primitive_types::U256;
 
-use crate::vaa::{parse_chain, parse_fixed, GovernanceAction, ShortUTFString};
-use crate::{parse_fixed_utf8, Chain, WormholeError};
+use alloc::vec::Vec;
+
+use crate::vaa::{
+    parse_chain, parse_fixed, parse_payload_id, parse_u256, parse_u8, GovernanceAction,
+    ParseResult, ShortUTFString,
+};
+use crate::{parse_fixed_utf8, serialize_fixed_utf8, Chain, WormholeError};
 
 /// Transfer is a message containing specifics detailing a token lock up on a sending chain. Chains
 /// that are attempting to initiate a transfer must lock up tokens in some manner, such as in a
 /// custody account or via burning, before emitting this message.
 #[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transfer {
     /// Amount being transferred (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::u256_dec"))]
     pub amount: U256,
 
     /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub token_address: [u8; 32],
 
     /// Chain ID of the token
     pub token_chain: Chain,
 
     /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub to: [u8; 32],
 
     /// Chain ID of the recipient
     pub to_chain: Chain,
 
     /// Amount of tokens (big-endian uint256) that the user is willing to pay as relayer fee. Must be <= Amount.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::u256_dec"))]
     pub fee: U256,
 }
 
 impl Transfer {
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
-        match parse_payload_transfer(input.as_ref()).finish() {
+        let input = input.as_ref();
+        match parse_payload_transfer(input).finish() {
             Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+            Err(e) => Err(e.locate(input)),
         }
     }
-}
 
-fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
-    // Parser Buffers.
-    let mut amount = [0u8; 32];
-    let mut fee = [0u8; 32];
+    /// Serialize to Wormhole wire format, including the payload ID.
+    pub fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut amount = [0u8; 32];
+        let mut fee = [0u8; 32];
+        self.amount.to_big_endian(&mut amount);
+        self.fee.to_big_endian(&mut fee);
 
+        let mut v = Vec::with_capacity(133);
+        v.push(0x1);
+        v.extend_from_slice(&amount);
+        v.extend_from_slice(&self.token_address);
+        v.extend_from_slice(&u16::from(self.token_chain).to_be_bytes());
+        v.extend_from_slice(&self.to);
+        v.extend_from_slice(&u16::from(self.to_chain).to_be_bytes());
+        v.extend_from_slice(&fee);
+        Ok(v)
+    }
+}
+
+pub(crate) fn parse_payload_transfer(input: &[u8]) -> ParseResult<'_, Transfer> {
     // Parse Payload.
-    let (i, _) = verify(u8, |&s| s == 0x1)(input)?;
-    let (i, _) = fill(u8, &mut amount)(i)?;
-    let (i, token_address) = parse_fixed(i)?;
-    let (i, token_chain) = parse_chain(i)?;
-    let (i, to) = parse_fixed(i)?;
-    let (i, to_chain) = parse_chain(i)?;
-    let (i, _) = fill(u8, &mut fee)(i)?;
+    let (i, _) = parse_payload_id(0x1)(input)?;
+    let (i, amount) = context("amount", parse_u256)(i)?;
+    let (i, token_address) = context("token_address", parse_fixed)(i)?;
+    let (i, token_chain) = context("token_chain", parse_chain)(i)?;
+    let (i, to) = context("to", parse_fixed)(i)?;
+    let (i, to_chain) = context("to_chain", parse_chain)(i)?;
+    let (i, fee) = context("fee", parse_u256)(i)?;
 
     Ok((
         i,
         Transfer {
-            amount: U256::from_big_endian(&amount),
+            amount,
+            token_address,
+            token_chain,
+            to,
+            to_chain,
+            fee,
+        },
+    ))
+}
+
+/// TransferWithPayload is a transfer that additionally carries an arbitrary payload for the
+/// recipient. The recipient is expected to be a contract which redeems the transfer itself, and
+/// `from_address` identifies the sender so that the recipient can authenticate the payload.
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferWithPayload {
+    /// Amount being transferred (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::u256_dec"))]
+    pub amount: U256,
+
+    /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
+    pub token_address: [u8; 32],
+
+    /// Chain ID of the token
+    pub token_chain: Chain,
+
+    /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
+    pub to: [u8; 32],
+
+    /// Chain ID of the recipient
+    pub to_chain: Chain,
+
+    /// Address of the sender. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
+    pub from_address: [u8; 32],
+
+    /// Arbitrary payload for the recipient, this is the remainder of the message.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
+    pub payload: Vec<u8>,
+}
+
+impl TransferWithPayload {
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        let input = input.as_ref();
+        match parse_payload_transfer_with_payload(input).finish() {
+            Ok(input) => Ok(input.1),
+            Err(e) => Err(e.locate(input)),
+        }
+    }
+
+    /// Serialize to Wormhole wire format, including the payload ID.
+    pub fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut amount = [0u8; 32];
+        self.amount.to_big_endian(&mut amount);
+
+        let mut v = Vec::with_capacity(133 + self.payload.len());
+        v.push(0x3);
+        v.extend_from_slice(&amount);
+        v.extend_from_slice(&self.token_address);
+        v.extend_from_slice(&u16::from(self.token_chain).to_be_bytes());
+        v.extend_from_slice(&self.to);
+        v.extend_from_slice(&u16::from(self.to_chain).to_be_bytes());
+        v.extend_from_slice(&self.from_address);
+        v.extend_from_slice(&self.payload);
+        Ok(v)
+    }
+}
+
+pub(crate) fn parse_payload_transfer_with_payload(
+    input: &[u8],
+) -> ParseResult<'_, TransferWithPayload> {
+    // Parse Payload.
+    let (i, _) = parse_payload_id(0x3)(input)?;
+    let (i, amount) = context("amount", parse_u256)(i)?;
+    let (i, token_address) = context("token_address", parse_fixed)(i)?;
+    let (i, token_chain) = context("token_chain", parse_chain)(i)?;
+    let (i, to) = context("to", parse_fixed)(i)?;
+    let (i, to_chain) = context("to_chain", parse_chain)(i)?;
+    let (i, from_address) = context("from_address", parse_fixed)(i)?;
+    let (i, payload) = rest(i)?;
+
+    Ok((
+        i,
+        TransferWithPayload {
+            amount,
             token_address,
             token_chain,
             to,
             to_chain,
-            fee: U256::from_big_endian(&fee),
+            from_address,
+            payload: payload.to_vec(),
         },
     ))
 }
 
 #[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AssetMeta {
     /// Address of the original token on the source chain.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub token_address: [u8; 32],
 
     /// Source Chain ID.
@@ -92,25 +205,39 @@ pub struct AssetMeta {
 
 impl AssetMeta {
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
-        match parse_payload_asset_meta(input.as_ref()).finish() {
+        let input = input.as_ref();
+        match parse_payload_asset_meta(input).finish() {
             Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+            Err(e) => Err(e.locate(input)),
         }
     }
+
+    /// Serialize to Wormhole wire format, including the payload ID. Symbol and name are truncated
+    /// to 32 bytes.
+    pub fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut v = Vec::with_capacity(100);
+        v.push(0x2);
+        v.extend_from_slice(&self.token_address);
+        v.extend_from_slice(&u16::from(self.token_chain).to_be_bytes());
+        v.push(self.decimals);
+        v.extend_from_slice(&serialize_fixed_utf8::<32>(&self.symbol));
+        v.extend_from_slice(&serialize_fixed_utf8::<32>(&self.name));
+        Ok(v)
+    }
 }
 
-fn parse_payload_asset_meta(input: &[u8]) -> IResult<&[u8], AssetMeta> {
+pub(crate) fn parse_payload_asset_meta(input: &[u8]) -> ParseResult<'_, AssetMeta> {
     // Parse Payload.
-    let (i, _) = verify(u8, |&s| s == 0x2)(input)?;
-    let (i, token_address) = parse_fixed(i)?;
-    let (i, token_chain) = parse_chain(i)?;
-    let (i, decimals) = u8(i)?;
-    let (i, symbol): (_, [u8; 32]) = parse_fixed(i)?;
-    let (i, name): (_, [u8; 32]) = parse_fixed(i)?;
+    let (i, _) = parse_payload_id(0x2)(input)?;
+    let (i, token_address) = context("token_address", parse_fixed)(i)?;
+    let (i, token_chain) = context("token_chain", parse_chain)(i)?;
+    let (i, decimals) = context("decimals", parse_u8)(i)?;
+    let (i, symbol): (_, [u8; 32]) = context("symbol", parse_fixed)(i)?;
+    let (i, name): (_, [u8; 32]) = context("name", parse_fixed)(i)?;
 
     // Name/Symbol should be UTF-8 strings, attempt to parse them by removing invalid bytes.
-    let symbol = parse_fixed_utf8::<_, 32>(symbol).unwrap();
-    let name = parse_fixed_utf8::<_, 32>(name).unwrap();
+    let symbol = parse_fixed_utf8(&symbol);
+    let name = parse_fixed_utf8(&name);
 
     Ok((
         i,
@@ -124,18 +251,63 @@ fn parse_payload_asset_meta(input: &[u8]) -> IResult<&[u8], AssetMeta> {
     ))
 }
 
+/// Amounts are sent over Wormhole with at most this many decimals, tokens with more decimals are
+/// truncated when sent and wrapped tokens are created with at most this many decimals.
+pub const MAX_DECIMALS: u8 = 8;
+
+/// Factor between an amount with `decimals` decimals and the same amount on the wire, `None` if
+/// the factor does not fit in a U256.
+fn amount_scale(decimals: u8) -> Option<U256> {
+    let exponent = decimals.saturating_sub(MAX_DECIMALS);
+    (0..exponent).try_fold(U256::one(), |scale, _| scale.checked_mul(U256::from(10)))
+}
+
+/// Convert an amount of a token with `decimals` decimals into the amount sent in a `Transfer`.
+/// Amounts are truncated to 8 decimals, the truncated dust is left with the sender by the token
+/// bridges and is returned by `dust_amount`. This is applied to both the amount and the fee.
+pub fn normalize_amount(amount: U256, decimals: u8) -> U256 {
+    match amount_scale(decimals) {
+        Some(scale) => amount / scale,
+        None => U256::zero(),
+    }
+}
+
+/// Convert an amount from a `Transfer` back into an amount of a token with `decimals` decimals,
+/// this is the amount paid out when the transfer is completed. `None` if the amount overflows.
+pub fn denormalize_amount(amount: U256, decimals: u8) -> Option<U256> {
+    if amount.is_zero() {
+        return Some(amount);
+    }
+    amount.checked_mul(amount_scale(decimals)?)
+}
+
+/// The part of an amount that cannot be represented with 8 decimals, which is not transferred.
+pub fn dust_amount(amount: U256, decimals: u8) -> U256 {
+    match amount_scale(decimals) {
+        Some(scale) => amount % scale,
+        None => amount,
+    }
+}
+
+/// Number of decimals of the wrapped token created for a token with `decimals` decimals.
+pub fn wrapped_decimals(decimals: u8) -> u8 {
+    decimals.min(MAX_DECIMALS)
+}
+
 #[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceRegisterChain {
     pub emitter: Chain,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub endpoint_address: [u8; 32],
 }
 
 impl GovernanceAction for GovernanceRegisterChain {
     const MODULE: &'static [u8] = b"TokenBridge";
     const ACTION: u8 = 1;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, emitter) = parse_chain(input)?;
-        let (i, endpoint_address) = parse_fixed(i)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, emitter) = context("emitter", parse_chain)(input)?;
+        let (i, endpoint_address) = context("endpoint_address", parse_fixed)(i)?;
         Ok((
             i,
             Self {
@@ -144,18 +316,236 @@ impl GovernanceAction for GovernanceRegisterChain {
             },
         ))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut v = Vec::with_capacity(34);
+        v.extend_from_slice(&u16::from(self.emitter).to_be_bytes());
+        v.extend_from_slice(&self.endpoint_address);
+        Ok(v)
+    }
 }
 
 #[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceContractUpgrade {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub new_contract: [u8; 32],
 }
 
 impl GovernanceAction for GovernanceContractUpgrade {
     const MODULE: &'static [u8] = b"TokenBridge";
     const ACTION: u8 = 2;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, new_contract) = parse_fixed(input)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, new_contract) = context("new_contract", parse_fixed)(input)?;
         Ok((i, Self { new_contract }))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        Ok(self.new_contract.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{
+        denormalize_amount, dust_amount, normalize_amount, wrapped_decimals, AssetMeta, Transfer,
+        TransferWithPayload,
+    };
+    use crate::{Chain, ParseErrorReason, WormholeError};
+    use primitive_types::U256;
+    use proptest::prelude::*;
+    use wormhole_conformance::{Names, TokenBridgePayload};
+
+    #[test]
+    fn test_transfer_round_trip() {
+        let payload = hex::decode("010000000000000000000000000000000000000000000000000000000005f5e10001000000000000000000000000000000000000000000000000000000757573640003000000000000000000000000f7f7dde848e7450a029cd0a9bd9bdae4b5147db3000300000000000000000000000000000000000000000000000000000000000f4240").unwrap();
+        let transfer = Transfer::from_bytes(&payload).unwrap();
+
+        assert_eq!(transfer.amount, U256::from(100_000_000u64));
+        assert_eq!(transfer.token_chain, Chain::Terra);
+        assert_eq!(transfer.to_chain, Chain::Terra);
+        assert_eq!(transfer.fee, U256::from(1_000_000u64));
+        assert_eq!(transfer.serialize().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_transfer_with_payload_round_trip() {
+        let transfer = TransferWithPayload {
+            amount: U256::from(100_000_000u64),
+            token_address: [1u8; 32],
+            token_chain: Chain::Ethereum,
+            to: [2u8; 32],
+            to_chain: Chain::Solana,
+            from_address: [3u8; 32],
+            payload: b"Hello World".to_vec(),
+        };
+
+        let payload = transfer.serialize().unwrap();
+        assert_eq!(payload[0], 3);
+        assert_eq!(payload.len(), 133 + 11);
+        assert_eq!(TransferWithPayload::from_bytes(&payload).unwrap(), transfer);
+
+        // An empty payload is valid, but the sender is not optional.
+        let empty = TransferWithPayload {
+            payload: vec![],
+            ..transfer
+        };
+        let payload = empty.serialize().unwrap();
+        assert_eq!(TransferWithPayload::from_bytes(&payload).unwrap(), empty);
+        assert!(TransferWithPayload::from_bytes(&payload[..100]).is_err());
+    }
+
+    #[test]
+    fn test_wrong_payload_id() {
+        let payload = hex::decode("02000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200021257455448000000000000000000000000000000000000000000000000000000005772617070656420457468657200000000000000000000000000000000000000").unwrap();
+        match Transfer::from_bytes(&payload) {
+            Err(WormholeError::ParseError(e)) => {
+                assert_eq!(e.field, "payload_id");
+                assert_eq!(e.offset, 0);
+                assert_eq!(e.reason, ParseErrorReason::InvalidValue);
+                assert_eq!(e.to_string(), "field payload_id at offset 0 is invalid");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_asset_meta_round_trip() {
+        let payload = hex::decode("02000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200021257455448000000000000000000000000000000000000000000000000000000005772617070656420457468657200000000000000000000000000000000000000").unwrap();
+        let meta = AssetMeta::from_bytes(&payload).unwrap();
+
+        assert_eq!(meta.token_chain, Chain::Ethereum);
+        assert_eq!(meta.decimals, 18);
+        assert_eq!(meta.symbol, "WETH");
+        assert_eq!(meta.name, "Wrapped Ether");
+        assert_eq!(meta.serialize().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_asset_meta_truncates_name() {
+        let meta = AssetMeta {
+            token_address: [1u8; 32],
+            token_chain: Chain::Solana,
+            decimals: 9,
+            symbol: "SOL".to_string(),
+            name: "A token name that is much longer than thirty two bytes".to_string(),
+        };
+
+        let parsed = AssetMeta::from_bytes(meta.serialize().unwrap()).unwrap();
+        assert_eq!(parsed.name, "A token name that is much longer");
+    }
+
+    /// Decode every case in the shared conformance corpus, the same corpus is decoded by the token
+    /// bridge contracts on each chain.
+    #[test]
+    fn test_conformance_corpus() {
+        let u256 = |v: U256| {
+            let mut out = [0u8; 32];
+            v.to_big_endian(&mut out);
+            out
+        };
+
+        wormhole_conformance::token_bridge(Names::Strict, |payload| match payload[0] {
+            1 => {
+                let t = Transfer::from_bytes(payload).unwrap();
+                TokenBridgePayload::Transfer {
+                    amount: u256(t.amount),
+                    token_address: t.token_address.to_vec(),
+                    token_chain: t.token_chain.into(),
+                    to: t.to.to_vec(),
+                    to_chain: t.to_chain.into(),
+                    fee: u256(t.fee),
+                }
+            }
+            3 => {
+                let t = TransferWithPayload::from_bytes(payload).unwrap();
+                TokenBridgePayload::TransferWithPayload {
+                    amount: u256(t.amount),
+                    token_address: t.token_address.to_vec(),
+                    token_chain: t.token_chain.into(),
+                    to: t.to.to_vec(),
+                    to_chain: t.to_chain.into(),
+                    from_address: t.from_address.to_vec(),
+                    payload: t.payload,
+                }
+            }
+            2 => {
+                let meta = AssetMeta::from_bytes(payload).unwrap();
+                TokenBridgePayload::AssetMeta {
+                    token_address: meta.token_address.to_vec(),
+                    token_chain: meta.token_chain.into(),
+                    decimals: meta.decimals,
+                    symbol: meta.symbol,
+                    name: meta.name,
+                }
+            }
+            other => panic!("unexpected payload ID {}", other),
+        });
+    }
+
+    #[test]
+    fn test_normalize_amount() {
+        // 1.123456789 of an 18 decimal token.
+        let amount = U256::from(1_123_456_789_000_000_000u64);
+        assert_eq!(normalize_amount(amount, 18), U256::from(112_345_678u64));
+        assert_eq!(dust_amount(amount, 18), U256::from(9_000_000_000u64));
+        assert_eq!(
+            denormalize_amount(U256::from(112_345_678u64), 18),
+            Some(U256::from(1_123_456_780_000_000_000u64))
+        );
+
+        // Tokens with 8 decimals or less are never truncated.
+        assert_eq!(normalize_amount(U256::from(123), 6), U256::from(123));
+        assert_eq!(
+            denormalize_amount(U256::from(123), 6),
+            Some(U256::from(123))
+        );
+        assert_eq!(wrapped_decimals(18), 8);
+        assert_eq!(wrapped_decimals(6), 6);
+
+        // Scales that do not fit in a U256 truncate everything.
+        assert_eq!(normalize_amount(U256::MAX, 255), U256::zero());
+        assert_eq!(dust_amount(U256::MAX, 255), U256::MAX);
+        assert_eq!(denormalize_amount(U256::one(), 255), None);
+        assert_eq!(denormalize_amount(U256::MAX, 9), None);
+    }
+
+    proptest! {
+        /// Matches the Solana token bridge, which truncates `u64` amounts in `transfer.rs`.
+        #[test]
+        fn prop_normalize_matches_solana(amount: u64, decimals in 0u8..=27) {
+            let trunc_divisor = 10u64.pow(8.max(decimals as u32) - 8);
+            let normalized = amount / trunc_divisor;
+            let amount_trunc = normalized * trunc_divisor;
+
+            prop_assert_eq!(normalize_amount(amount.into(), decimals), normalized.into());
+            prop_assert_eq!(
+                denormalize_amount(normalized.into(), decimals),
+                Some(amount_trunc.into())
+            );
+            prop_assert_eq!(dust_amount(amount.into(), decimals), (amount - amount_trunc).into());
+        }
+
+        /// Matches the CosmWasm token bridge, which removes dust from `u128` amounts before
+        /// scaling them down, and scales them back up when transfers complete.
+        #[test]
+        fn prop_normalize_matches_cosmwasm(amount: u128, decimals in 0u8..=46) {
+            let multiplier = 10u128.pow((decimals.max(8) - 8) as u32);
+            let chopped = amount - amount % multiplier;
+
+            let normalized = normalize_amount(amount.into(), decimals);
+            prop_assert_eq!(normalized, (chopped / multiplier).into());
+            prop_assert_eq!(denormalize_amount(normalized, decimals), Some(chopped.into()));
+            prop_assert_eq!(dust_amount(amount.into(), decimals), (amount % multiplier).into());
+        }
+
+        #[test]
+        fn prop_normalize_round_trip(amount: [u8; 32], decimals: u8) {
+            let amount = U256::from_big_endian(&amount);
+            let normalized = normalize_amount(amount, decimals);
+            let dust = dust_amount(amount, decimals);
+            prop_assert_eq!(denormalize_amount(normalized, decimals).unwrap() + dust, amount);
+            prop_assert_eq!(normalize_amount(amount - dust, decimals), normalized);
+        }
+    }
 }
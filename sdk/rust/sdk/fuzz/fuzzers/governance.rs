@@ -1,7 +1,25 @@
 #![no_main]
 use libfuzzer_sys::fuzz_target;
-use wormhole_sdk::VAA;
+use wormhole_sdk::vaa::{core, nft, token, GovernanceAction};
+use wormhole_sdk::Payload;
+
+/// Parse the input as the given action, any action that parses must serialize back into the same
+/// bytes, header included.
+fn check<A: GovernanceAction>(data: &[u8]) {
+    if let Ok((header, action)) = A::from_bytes(data, None) {
+        let bytes = action.to_bytes(header.chains).unwrap();
+        assert_eq!(bytes, &data[..bytes.len()]);
+    }
+}
 
 fuzz_target!(|data: &[u8]| {
-    VAA::from_bytes(data);
+    check::<core::GovernanceContractUpgrade>(data);
+    check::<core::GovernanceGuardianSetChange>(data);
+    check::<core::GovernanceSetMessageFee>(data);
+    check::<core::GovernanceTransferFees>(data);
+    check::<token::GovernanceRegisterChain>(data);
+    check::<token::GovernanceContractUpgrade>(data);
+    check::<nft::GovernanceRegisterChain>(data);
+    check::<nft::GovernanceContractUpgrade>(data);
+    let _ = Payload::from_bytes(data);
 });
@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use wormhole_sdk::vaa::nft::Transfer;
+
+fuzz_target!(|data: &[u8]| {
+    // Invalid UTF-8 is stripped from the symbol and name, so only the parsed form is stable.
+    if let Ok(transfer) = Transfer::from_bytes(data) {
+        let bytes = transfer.serialize().unwrap();
+        assert_eq!(Transfer::from_bytes(&bytes).unwrap(), transfer);
+    }
+});
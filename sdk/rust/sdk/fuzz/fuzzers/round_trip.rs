@@ -0,0 +1,263 @@
+#![no_main]
+//! Structure-aware round trips: generate well-formed messages with `arbitrary`, serialize them and
+//! assert that parsing the result produces the original message.
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use primitive_types::U256;
+use wormhole_sdk::vaa::{core, nft, token, GovernanceAction};
+use wormhole_sdk::{Chain, VAA};
+
+#[derive(Arbitrary, Debug)]
+enum Message {
+    Vaa {
+        guardian_set_index: u32,
+        signatures: Vec<[u8; 66]>,
+        timestamp: u32,
+        nonce: u32,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        sequence: u64,
+        consistency_level: u8,
+        payload: Vec<u8>,
+    },
+    TokenTransfer {
+        amount: [u8; 32],
+        token_address: [u8; 32],
+        token_chain: u16,
+        to: [u8; 32],
+        to_chain: u16,
+        fee: [u8; 32],
+    },
+    TokenTransferWithPayload {
+        amount: [u8; 32],
+        token_address: [u8; 32],
+        token_chain: u16,
+        to: [u8; 32],
+        to_chain: u16,
+        from_address: [u8; 32],
+        payload: Vec<u8>,
+    },
+    TokenAssetMeta {
+        token_address: [u8; 32],
+        token_chain: u16,
+        decimals: u8,
+        symbol: String,
+        name: String,
+    },
+    NftTransfer {
+        nft_address: [u8; 32],
+        nft_chain: u16,
+        symbol: String,
+        name: String,
+        token_id: [u8; 32],
+        uri: String,
+        to: [u8; 32],
+        to_chain: u16,
+    },
+    Governance {
+        chain: u16,
+        action: Action,
+    },
+}
+
+#[derive(Arbitrary, Debug)]
+enum Action {
+    CoreContractUpgrade([u8; 32]),
+    CoreGuardianSetChange(u32, Vec<[u8; 20]>),
+    CoreSetMessageFee([u8; 32]),
+    CoreTransferFees([u8; 32], [u8; 32]),
+    TokenRegisterChain(u16, [u8; 32]),
+    TokenContractUpgrade([u8; 32]),
+    NftRegisterChain(u16, [u8; 32]),
+    NftContractUpgrade([u8; 32]),
+}
+
+/// Truncate a string to at most `max` bytes on a character boundary.
+fn truncate(s: impl Iterator<Item = char>, max: usize) -> String {
+    let mut out = String::new();
+    for c in s {
+        if out.len() + c.len_utf8() > max {
+            break;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Fixed width string fields are zero padded and stripped of invalid UTF-8 when parsed, only
+/// strings that survive this unchanged can be expected to round trip.
+fn fixed_utf8(s: &str) -> String {
+    truncate(s.chars().filter(|&c| c != '\0' && c != '\u{FFFD}'), 32)
+}
+
+fn check<A: GovernanceAction + PartialEq + std::fmt::Debug>(chain: Chain, action: A) {
+    // Guardian set changes fail to serialize with more than 255 guardians.
+    if let Ok(bytes) = action.to_bytes(chain) {
+        let (header, parsed) = A::from_bytes(&bytes, Some(chain)).unwrap();
+        assert_eq!(header.chains, chain);
+        assert_eq!(parsed, action);
+    }
+}
+
+fuzz_target!(|message: Message| {
+    match message {
+        Message::Vaa {
+            guardian_set_index,
+            signatures,
+            timestamp,
+            nonce,
+            emitter_chain,
+            emitter_address,
+            sequence,
+            consistency_level,
+            payload,
+        } => {
+            let vaa = VAA {
+                version: 1,
+                guardian_set_index,
+                signatures,
+                timestamp,
+                nonce,
+                emitter_chain: Chain::from(emitter_chain),
+                emitter_address,
+                sequence,
+                consistency_level,
+                payload,
+            };
+
+            // VAA's with more than 255 signatures cannot be serialized.
+            if let Ok(bytes) = vaa.to_bytes() {
+                assert_eq!(VAA::from_bytes(&bytes).unwrap(), vaa);
+            }
+        }
+
+        Message::TokenTransfer {
+            amount,
+            token_address,
+            token_chain,
+            to,
+            to_chain,
+            fee,
+        } => {
+            let transfer = token::Transfer {
+                amount: U256::from_big_endian(&amount),
+                token_address,
+                token_chain: Chain::from(token_chain),
+                to,
+                to_chain: Chain::from(to_chain),
+                fee: U256::from_big_endian(&fee),
+            };
+            let bytes = transfer.serialize().unwrap();
+            assert_eq!(token::Transfer::from_bytes(&bytes).unwrap(), transfer);
+        }
+
+        Message::TokenTransferWithPayload {
+            amount,
+            token_address,
+            token_chain,
+            to,
+            to_chain,
+            from_address,
+            payload,
+        } => {
+            let transfer = token::TransferWithPayload {
+                amount: U256::from_big_endian(&amount),
+                token_address,
+                token_chain: Chain::from(token_chain),
+                to,
+                to_chain: Chain::from(to_chain),
+                from_address,
+                payload,
+            };
+            let bytes = transfer.serialize().unwrap();
+            assert_eq!(
+                token::TransferWithPayload::from_bytes(&bytes).unwrap(),
+                transfer
+            );
+        }
+
+        Message::TokenAssetMeta {
+            token_address,
+            token_chain,
+            decimals,
+            symbol,
+            name,
+        } => {
+            let meta = token::AssetMeta {
+                token_address,
+                token_chain: Chain::from(token_chain),
+                decimals,
+                symbol: fixed_utf8(&symbol),
+                name: fixed_utf8(&name),
+            };
+            let bytes = meta.serialize().unwrap();
+            assert_eq!(token::AssetMeta::from_bytes(&bytes).unwrap(), meta);
+        }
+
+        Message::NftTransfer {
+            nft_address,
+            nft_chain,
+            symbol,
+            name,
+            token_id,
+            uri,
+            to,
+            to_chain,
+        } => {
+            let transfer = nft::Transfer {
+                nft_address,
+                nft_chain: Chain::from(nft_chain),
+                symbol: fixed_utf8(&symbol),
+                name: fixed_utf8(&name),
+                token_id: U256::from_big_endian(&token_id),
+                uri: truncate(uri.chars(), 255),
+                to,
+                to_chain: Chain::from(to_chain),
+            };
+            let bytes = transfer.serialize().unwrap();
+            assert_eq!(nft::Transfer::from_bytes(&bytes).unwrap(), transfer);
+        }
+
+        Message::Governance { chain, action } => {
+            let chain = Chain::from(chain);
+            match action {
+                Action::CoreContractUpgrade(new_contract) => {
+                    check(chain, core::GovernanceContractUpgrade::new(new_contract))
+                }
+                Action::CoreGuardianSetChange(index, guardians) => check(
+                    chain,
+                    core::GovernanceGuardianSetChange::new(index, guardians),
+                ),
+                Action::CoreSetMessageFee(fee) => check(
+                    chain,
+                    core::GovernanceSetMessageFee::new(U256::from_big_endian(&fee)),
+                ),
+                Action::CoreTransferFees(amount, to) => check(
+                    chain,
+                    core::GovernanceTransferFees::new(U256::from_big_endian(&amount), to),
+                ),
+                Action::TokenRegisterChain(emitter, endpoint_address) => check(
+                    chain,
+                    token::GovernanceRegisterChain {
+                        emitter: Chain::from(emitter),
+                        endpoint_address,
+                    },
+                ),
+                Action::TokenContractUpgrade(new_contract) => {
+                    check(chain, token::GovernanceContractUpgrade { new_contract })
+                }
+                Action::NftRegisterChain(emitter, endpoint_address) => check(
+                    chain,
+                    nft::GovernanceRegisterChain {
+                        emitter: Chain::from(emitter),
+                        endpoint_address,
+                    },
+                ),
+                Action::NftContractUpgrade(new_contract) => {
+                    check(chain, nft::GovernanceContractUpgrade { new_contract })
+                }
+            }
+        }
+    }
+});
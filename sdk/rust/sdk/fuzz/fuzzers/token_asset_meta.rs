@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use wormhole_sdk::vaa::token::AssetMeta;
+
+fuzz_target!(|data: &[u8]| {
+    // Invalid UTF-8 is stripped from the symbol and name, so only the parsed form is stable.
+    if let Ok(meta) = AssetMeta::from_bytes(data) {
+        let bytes = meta.serialize().unwrap();
+        assert_eq!(AssetMeta::from_bytes(&bytes).unwrap(), meta);
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use wormhole_sdk::vaa::token::{Transfer, TransferWithPayload};
+
+fuzz_target!(|data: &[u8]| {
+    // Transfers have no lossy fields, so a parsed transfer serializes back into its input.
+    if let Ok(transfer) = Transfer::from_bytes(data) {
+        let bytes = transfer.serialize().unwrap();
+        assert_eq!(bytes, &data[..bytes.len()]);
+    }
+
+    if let Ok(transfer) = TransferWithPayload::from_bytes(data) {
+        assert_eq!(transfer.serialize().unwrap(), data);
+    }
+});
@@ -1,7 +1,15 @@
 #![no_main]
 use libfuzzer_sys::fuzz_target;
-use wormhole_sdk::vaa::VAA;
+use wormhole_sdk::{VAARef, VAA};
 
 fuzz_target!(|data: &[u8]| {
-    VAA::from_bytes(data);
+    // The owned and borrowed parsers must agree on what is a valid VAA.
+    match (VAA::from_bytes(data), VAARef::from_bytes(data)) {
+        (Ok(vaa), Ok(view)) => {
+            assert_eq!(view.to_vaa(), vaa);
+            let _ = vaa.parse_payload();
+        }
+        (Err(_), Err(_)) => {}
+        _ => panic!("VAA and VAARef disagree"),
+    }
 });
@@ -1,6 +1,16 @@
 //! Exposes an API implementation depending on which feature flags have been toggled for the
 //! library. Check submodules for chain runtime specific documentation.
 
+#[cfg(feature = "cosmwasm")]
+pub mod cosmwasm;
+#[cfg(feature = "cosmwasm")]
+pub use cosmwasm::*;
+
+#[cfg(feature = "near")]
+pub mod near;
+#[cfg(feature = "near")]
+pub use near::*;
+
 #[cfg(feature = "solana")]
 pub mod solana;
 #[cfg(feature = "solana")]
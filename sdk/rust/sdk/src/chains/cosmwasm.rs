@@ -0,0 +1,88 @@
+//! Helpers for the Wormhole core contract on CosmWasm 1.x chains, such as Terra 2, Injective and
+//! XPLA. Unlike the `terra` module these do not assume a network, the core contract address is
+//! passed to each helper so that a single build can target any chain, usually by reading it from
+//! the integrating contract's config.
+
+use cosmwasm_std_1::{
+    to_binary, Addr, Binary, Coin, CosmosMsg, Deps, Env, QueryRequest, StdResult, WasmMsg,
+    WasmQuery,
+};
+use serde::de::DeserializeOwned;
+
+pub use wormhole_bridge_cosmwasm::msg::{
+    ExecuteMsg, GetAddressHexResponse, GetStateResponse, GuardianSetInfoResponse, QueryMsg,
+};
+pub use wormhole_bridge_cosmwasm::state::ParsedVAA;
+
+/// Build the message that posts `message` through the core contract at `wormhole`. The fee
+/// returned by `message_fee` must be attached as `funds` unless it is zero.
+pub fn post_message(
+    wormhole: &Addr,
+    nonce: u32,
+    message: impl AsRef<[u8]>,
+    funds: Vec<Coin>,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: wormhole.to_string(),
+        funds,
+        msg: to_binary(&ExecuteMsg::PostMessage {
+            message: Binary::from(message.as_ref()),
+            nonce,
+        })?,
+    }))
+}
+
+/// Build the message that submits a governance VAA to the core contract.
+pub fn submit_vaa(wormhole: &Addr, vaa: impl AsRef<[u8]>) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: wormhole.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::SubmitVAA {
+            vaa: Binary::from(vaa.as_ref()),
+        })?,
+    }))
+}
+
+/// Parse and verify a VAA using the Wormhole contract Query interface, this fails if the VAA is
+/// not signed by the current guardian set.
+pub fn parse_vaa(deps: Deps, env: &Env, wormhole: &Addr, vaa: &Binary) -> StdResult<ParsedVAA> {
+    query(
+        deps,
+        wormhole,
+        &QueryMsg::VerifyVAA {
+            vaa: vaa.clone(),
+            block_time: env.block.time.seconds(),
+        },
+    )
+}
+
+/// Query the current guardian set.
+pub fn guardian_set_info(deps: Deps, wormhole: &Addr) -> StdResult<GuardianSetInfoResponse> {
+    query(deps, wormhole, &QueryMsg::GuardianSetInfo {})
+}
+
+/// Query the fee that must be attached when posting a message.
+pub fn message_fee(deps: Deps, wormhole: &Addr) -> StdResult<Coin> {
+    let state: GetStateResponse = query(deps, wormhole, &QueryMsg::GetState {})?;
+    Ok(state.fee)
+}
+
+/// Query the hex encoded Wormhole address of a contract on this chain, this is the emitter address
+/// found in VAA's produced by messages posted from `address`.
+pub fn address_hex(deps: Deps, wormhole: &Addr, address: &Addr) -> StdResult<String> {
+    let response: GetAddressHexResponse = query(
+        deps,
+        wormhole,
+        &QueryMsg::QueryAddressHex {
+            address: address.to_string(),
+        },
+    )?;
+    Ok(response.hex)
+}
+
+fn query<T: DeserializeOwned>(deps: Deps, wormhole: &Addr, msg: &QueryMsg) -> StdResult<T> {
+    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: wormhole.to_string(),
+        msg: to_binary(msg)?,
+    }))
+}
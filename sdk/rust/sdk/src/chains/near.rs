@@ -0,0 +1,170 @@
+use near_sdk::serde_json::json;
+use near_sdk::{env, ext_contract, AccountId, Balance, Gas, GasWeight, Promise, PromiseOrValue};
+use sha2::{Digest, Sha256};
+use wormhole_core::{Chain, Network};
+
+/// Core Contract Address on the given network.
+pub fn id_for(network: Network) -> AccountId {
+    network.core(Chain::Near).unwrap().parse().unwrap()
+}
+
+/// Export Core Mainnet Contract Address
+#[cfg(feature = "mainnet")]
+pub fn id() -> AccountId {
+    id_for(Network::Mainnet)
+}
+
+/// Export Core Testnet Contract Address
+#[cfg(feature = "testnet")]
+pub fn id() -> AccountId {
+    id_for(Network::Testnet)
+}
+
+/// Export Local Tilt Devnet Contract Address
+#[cfg(feature = "devnet")]
+pub fn id() -> AccountId {
+    id_for(Network::Devnet)
+}
+
+/// The Wormhole contract refuses to publish messages with less prepaid gas than this.
+pub const PUBLISH_MESSAGE_GAS: Gas = Gas(10_000_000_000_000);
+
+/// Gas attached to `verify_vaa` calls, verification cost grows with the size of the guardian set.
+pub const VERIFY_VAA_GAS: Gas = Gas(30_000_000_000_000);
+
+/// Interface of the Wormhole core contract, use this directly for calls not covered by the helpers
+/// below.
+#[ext_contract(ext_wormhole)]
+pub trait Wormhole {
+    fn verify_vaa(&self, vaa: String) -> u32;
+    fn publish_message(&mut self, data: String, nonce: u32) -> u64;
+    fn register_emitter(&mut self, emitter: String) -> PromiseOrValue<bool>;
+    fn message_fee(&self) -> u128;
+}
+
+/// Emitters on NEAR are identified by the SHA-256 hash of their account ID, this is the emitter
+/// address found in VAA's produced by messages published from `account`.
+pub fn emitter_address(account: &AccountId) -> [u8; 32] {
+    Sha256::digest(account.as_str().as_bytes()).into()
+}
+
+/// JSON arguments of a `register_emitter` call for `emitter`.
+pub fn register_emitter_args(emitter: &AccountId) -> Vec<u8> {
+    json!({ "emitter": emitter }).to_string().into_bytes()
+}
+
+/// JSON arguments of a `publish_message` call, the contract expects the payload hex encoded.
+pub fn publish_message_args(nonce: u32, payload: impl AsRef<[u8]>) -> Vec<u8> {
+    json!({ "data": hex::encode(payload), "nonce": nonce })
+        .to_string()
+        .into_bytes()
+}
+
+/// JSON arguments of a `verify_vaa` call, the contract expects the VAA hex encoded.
+pub fn verify_vaa_args(vaa: impl AsRef<[u8]>) -> Vec<u8> {
+    json!({ "vaa": hex::encode(vaa) }).to_string().into_bytes()
+}
+
+/// Register the current contract as an emitter, this must be done once before publishing. The
+/// deposit pays for the storage used by the emitter's sequence number, any excess is refunded.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
+pub fn register_emitter(deposit: Balance) -> Promise {
+    register_emitter_with(id(), deposit)
+}
+
+/// Same as `register_emitter`, but registers with the contract at `wormhole`.
+pub fn register_emitter_with(wormhole: AccountId, deposit: Balance) -> Promise {
+    Promise::new(wormhole).function_call_weight(
+        "register_emitter".to_string(),
+        register_emitter_args(&env::current_account_id()),
+        deposit,
+        Gas(0),
+        GasWeight::default(),
+    )
+}
+
+/// Query the fee that must be attached to each `publish_message` call.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
+pub fn message_fee() -> Promise {
+    message_fee_from(id())
+}
+
+/// Same as `message_fee`, but queries the contract at `wormhole`.
+pub fn message_fee_from(wormhole: AccountId) -> Promise {
+    ext_wormhole::ext(wormhole).message_fee()
+}
+
+/// Publish a message from the current contract, the promise resolves to the message sequence
+/// number. `message_fee` is attached as the deposit and must be at least the fee returned by
+/// `message_fee`, the Wormhole contract keeps the full deposit.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
+pub fn publish_message(nonce: u32, payload: impl AsRef<[u8]>, message_fee: Balance) -> Promise {
+    publish_message_to(id(), nonce, payload, message_fee)
+}
+
+/// Same as `publish_message`, but publishes through the contract at `wormhole`.
+pub fn publish_message_to(
+    wormhole: AccountId,
+    nonce: u32,
+    payload: impl AsRef<[u8]>,
+    message_fee: Balance,
+) -> Promise {
+    Promise::new(wormhole).function_call_weight(
+        "publish_message".to_string(),
+        publish_message_args(nonce, payload),
+        message_fee,
+        PUBLISH_MESSAGE_GAS,
+        GasWeight::default(),
+    )
+}
+
+/// Verify a VAA with the Wormhole contract, the promise fails if the VAA is invalid and otherwise
+/// resolves to the index of the guardian set that verified it. Chain a callback onto this promise
+/// to act on the VAA once verified, parsing it with `VAA::from_bytes`.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
+pub fn verify_vaa(vaa: impl AsRef<[u8]>) -> Promise {
+    verify_vaa_with(id(), vaa)
+}
+
+/// Same as `verify_vaa`, but verifies with the contract at `wormhole`.
+pub fn verify_vaa_with(wormhole: AccountId, vaa: impl AsRef<[u8]>) -> Promise {
+    Promise::new(wormhole).function_call_weight(
+        "verify_vaa".to_string(),
+        verify_vaa_args(vaa),
+        0,
+        VERIFY_VAA_GAS,
+        GasWeight::default(),
+    )
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_emitter_address() {
+        // The mainnet token bridge, its emitter address is registered on every other chain.
+        let account: AccountId = "contract.portalbridge.near".parse().unwrap();
+        assert_eq!(
+            hex::encode(emitter_address(&account)),
+            "148410499d3fcda4dcfd68a1ebfcdddda16ab28326448d4aae4d2f0465cdfcb7"
+        );
+    }
+
+    #[test]
+    fn test_call_args() {
+        let emitter: AccountId = "emitter.test.near".parse().unwrap();
+        assert_eq!(
+            register_emitter_args(&emitter),
+            br#"{"emitter":"emitter.test.near"}"#.to_vec()
+        );
+        assert_eq!(
+            publish_message_args(42, [0xde, 0xad]),
+            br#"{"data":"dead","nonce":42}"#.to_vec()
+        );
+        assert_eq!(
+            verify_vaa_args([0x01, 0x00, 0xff]),
+            br#"{"vaa":"0100ff"}"#.to_vec()
+        );
+    }
+}
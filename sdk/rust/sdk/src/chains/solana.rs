@@ -1,8 +1,12 @@
 use borsh::BorshDeserialize;
 use solana_program::account_info::AccountInfo;
 use solana_program::entrypoint::ProgramResult;
-use solana_program::program::invoke_signed;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+use solana_program::sysvar::Sysvar;
 use std::str::FromStr;
 
 // Export Bridge API
@@ -16,25 +20,33 @@ pub use bridge::PostVAAData;
 pub use bridge::PostedVAAData;
 pub use bridge::VerifySignaturesData;
 
-use wormhole_core::WormholeError;
+use wormhole_core::require;
+use wormhole_core::Chain;
+use wormhole_core::Network;
+use wormhole_core::WormholeError::{self, AlreadyClaimed, InvalidAccount, InvalidEmitter};
 use wormhole_core::VAA;
 
+/// Core Contract Address on the given network.
+pub fn id_for(network: Network) -> Pubkey {
+    Pubkey::from_str(network.core(Chain::Solana).unwrap()).unwrap()
+}
+
 /// Export Core Mainnet Contract Address
 #[cfg(feature = "mainnet")]
 pub fn id() -> Pubkey {
-    Pubkey::from_str("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth").unwrap()
+    id_for(Network::Mainnet)
 }
 
-/// Export Core Devnet Contract Address
+/// Export Core Testnet Contract Address
 #[cfg(feature = "testnet")]
 pub fn id() -> Pubkey {
-    Pubkey::from_str("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5").unwrap()
+    id_for(Network::Testnet)
 }
 
 /// Export Local Tilt Devnet Contract Address
 #[cfg(feature = "devnet")]
 pub fn id() -> Pubkey {
-    Pubkey::from_str("Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o").unwrap()
+    id_for(Network::Devnet)
 }
 
 /// Derives the Wormhole configuration account address.
@@ -50,6 +62,12 @@ pub fn fee_collector(id: &Pubkey) -> Pubkey {
     fee_collector
 }
 
+/// Derives the Wormhole fee schedule address, which must be passed along with the other accounts
+/// when posting messages so the bridge can charge the per byte fee.
+pub fn fee_schedule(id: &Pubkey) -> Pubkey {
+    bridge::accounts::fee_schedule_key(id)
+}
+
 /// Derives the sequence address for an emitter, which is incremented after each message post.
 pub fn sequence(id: &Pubkey, emitter: &Pubkey) -> Pubkey {
     let (sequence, _) = Pubkey::find_program_address(&[b"Sequence", &emitter.to_bytes()], &id);
@@ -64,6 +82,42 @@ pub fn emitter(id: &Pubkey) -> (Pubkey, Vec<&[u8]>, u8) {
     (emitter, seeds.to_vec(), bump)
 }
 
+/// Derives the address of the account a VAA is posted to, from the hash of the VAA body. See
+/// `VAA::digest` for computing the hash of a VAA.
+pub fn posted_vaa(id: &Pubkey, hash: &[u8; 32]) -> Pubkey {
+    let (posted_vaa, _) = Pubkey::find_program_address(&[b"PostedVAA", hash], id);
+    posted_vaa
+}
+
+/// Derives the claim address for a VAA under the consuming program, and its bump seed. Claims are
+/// derived in the same way as the claims the bridge itself creates for governance VAA's.
+pub fn claim(program_id: &Pubkey, vaa: &PostedVAAData) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            &vaa.emitter_address,
+            &vaa.emitter_chain.to_be_bytes(),
+            &vaa.sequence.to_be_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Hash of the body of a posted VAA, this is the hash the guardians signed over and that the
+/// account address is derived from.
+pub fn posted_vaa_hash(vaa: &PostedVAAData) -> [u8; 32] {
+    let body = VAA {
+        timestamp: vaa.vaa_time,
+        nonce: vaa.nonce,
+        emitter_chain: Chain::from(vaa.emitter_chain),
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload.clone(),
+        ..Default::default()
+    };
+    body.digest().unwrap().hash
+}
+
 /// Deserialize helper the BridgeConfig from a Wormhole config account.
 pub fn read_config(config: &AccountInfo) -> Result<BridgeConfig, WormholeError> {
     let bridge_data = BridgeData::try_from_slice(&config.data.borrow())
@@ -77,9 +131,95 @@ pub fn read_vaa(vaa: &AccountInfo) -> Result<PostedVAAData, WormholeError> {
         .map_err(|_| WormholeError::DeserializeFailed)?)
 }
 
+/// Read a posted VAA and check that it can be trusted: the account must be owned by the bridge,
+/// hold a posted VAA rather than a posted message, and live at the address derived from the hash
+/// of its body. The emitter is checked against the expected chain and address, which would
+/// usually be read from a registration account owned by the calling program.
+pub fn read_and_validate_vaa(
+    id: &Pubkey,
+    vaa: &AccountInfo,
+    emitter_chain: Chain,
+    emitter_address: &[u8; 32],
+) -> Result<PostedVAAData, WormholeError> {
+    require!(vaa.owner == id, InvalidAccount);
+
+    // PostedVAAData also deserializes posted messages, which are not signed by the guardians.
+    require!(vaa.data.borrow().starts_with(b"vaa"), InvalidAccount);
+    let data = read_vaa(vaa)?;
+    require!(
+        *vaa.key == posted_vaa(id, &posted_vaa_hash(&data)),
+        InvalidAccount
+    );
+
+    let valid_emitter = Chain::from(data.emitter_chain) == emitter_chain
+        && data.emitter_address == *emitter_address;
+    require!(valid_emitter, InvalidEmitter);
+    Ok(data)
+}
+
+/// Mark a VAA as consumed by creating its claim account, owned by `program_id`. This fails if the
+/// VAA has already been claimed, which protects the calling program against replays. `payer` pays
+/// the rent for the claim and must be a signer, `accounts` must include the system program.
+pub fn consume_claim(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    claim: &AccountInfo,
+    vaa: &PostedVAAData,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let (address, bump) = self::claim(program_id, vaa);
+    if *claim.key != address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // An existing claim account means this VAA has been consumed before.
+    if !claim.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // The claim holds a single borsh encoded `true`, matching the bridge's own ClaimData.
+    let size = 1;
+    let seeds: &[&[u8]] = &[
+        &vaa.emitter_address,
+        &vaa.emitter_chain.to_be_bytes(),
+        &vaa.sequence.to_be_bytes(),
+        &[bump],
+    ];
+
+    // Anyone can send lamports to the claim address ahead of time, which would make a plain
+    // `create_account` fail and block the VAA from ever being claimed. Instead top up to the rent
+    // exempt balance and then allocate and assign, as solitaire does for the bridge's accounts.
+    let rent = Rent::get()?.minimum_balance(size);
+    if claim.lamports() < rent {
+        invoke(
+            &system_instruction::transfer(payer, claim.key, rent - claim.lamports()),
+            accounts,
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(claim.key, size as u64),
+        accounts,
+        &[seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(claim.key, program_id),
+        accounts,
+        &[seeds],
+    )?;
+    claim.data.borrow_mut()[0] = 1;
+    Ok(())
+}
+
+/// Check whether a claim account shows its VAA as consumed, without modifying it.
+pub fn check_unclaimed(claim: &AccountInfo) -> Result<(), WormholeError> {
+    require!(claim.data_is_empty(), AlreadyClaimed);
+    Ok(())
+}
+
 /// This helper method wraps the steps required to invoke Wormhole, it takes care of fee payment,
 /// emitter derivation, and function invocation. This will be the right thing to use if you need to
 /// simply emit a message in the most straight forward way possible.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
 pub fn post_message(
     program_id: Pubkey,
     payer: Pubkey,
@@ -89,10 +229,35 @@ pub fn post_message(
     pda_seeds: Option<&[&[&[u8]]]>,
     accounts: &[AccountInfo],
     nonce: u32,
+) -> ProgramResult {
+    post_message_to(
+        id(),
+        program_id,
+        payer,
+        message,
+        payload,
+        consistency,
+        pda_seeds,
+        accounts,
+        nonce,
+    )
+}
+
+/// Same as `post_message`, but posts to the bridge at `id` which allows the network to be chosen
+/// at runtime with `id_for`.
+pub fn post_message_to(
+    id: Pubkey,
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    payload: impl AsRef<[u8]>,
+    consistency: ConsistencyLevel,
+    pda_seeds: Option<&[&[&[u8]]]>,
+    accounts: &[AccountInfo],
+    nonce: u32,
 ) -> ProgramResult {
     // Derive any necessary Pubkeys, derivation makes sure that we match the accounts that are being
     // provided by the user as well.
-    let id = id();
     let fee_collector = fee_collector(&id);
     let (emitter, mut emitter_seeds, bump) = emitter(&program_id);
     let bump = &[bump];
@@ -108,9 +273,11 @@ pub fn post_message(
         seeds.extend(v);
     }
 
-    // Pay Fee to the Wormhole
+    // Pay Fee to the Wormhole, which depends on the payload length.
+    let fee =
+        bridge::accounts::message_fee(&id, &config, &emitter, accounts, payload.as_ref().len())?;
     invoke_signed(
-        &solana_program::system_instruction::transfer(&payer, &fee_collector, config.fee),
+        &system_instruction::transfer(&payer, &fee_collector, fee),
         accounts,
         &[],
     )?;
@@ -133,3 +300,229 @@ pub fn post_message(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use borsh::BorshSerialize;
+    use solana_program::instruction::Instruction;
+    use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use std::cell::RefCell;
+    use std::sync::Once;
+
+    thread_local! {
+        static INVOKED: RefCell<Vec<Instruction>> = RefCell::new(Vec::new());
+    }
+
+    /// Records cross program invocations instead of executing them, except that `allocate` resizes
+    /// the target account so the claim can be written to afterwards.
+    struct Stubs;
+
+    impl SyscallStubs for Stubs {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            let target = instruction.accounts[0].pubkey;
+            if *instruction == system_instruction::allocate(&target, 1) {
+                let info = account_infos.iter().find(|a| *a.key == target).unwrap();
+                *info.data.borrow_mut() = Box::leak(vec![0u8; 1].into_boxed_slice());
+            }
+            INVOKED.with(|i| i.borrow_mut().push(instruction.clone()));
+            Ok(())
+        }
+
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            solana_program::entrypoint::SUCCESS
+        }
+    }
+
+    fn setup() {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            let _ = set_syscall_stubs(Box::new(Stubs));
+        });
+        INVOKED.with(|i| i.borrow_mut().clear());
+    }
+
+    fn message() -> MessageData {
+        MessageData {
+            vaa_version: 1,
+            consistency_level: 1,
+            vaa_time: 1656000000,
+            nonce: 7,
+            sequence: 10,
+            emitter_chain: 2,
+            emitter_address: [4u8; 32],
+            payload: b"payload".to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn account_data(magic: &[u8], message: &MessageData) -> Vec<u8> {
+        let mut data = magic.to_vec();
+        data.extend(message.try_to_vec().unwrap());
+        data
+    }
+
+    #[test]
+    fn test_read_and_validate_vaa() {
+        let id = Pubkey::new_unique();
+        let mut data = account_data(b"vaa", &message());
+        let key = posted_vaa(&id, &posted_vaa_hash(&read_vaa_data(&data)));
+        let mut lamports = 0;
+        let vaa = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &id, false, 0);
+
+        let parsed = read_and_validate_vaa(&id, &vaa, Chain::Ethereum, &[4u8; 32]).unwrap();
+        assert_eq!(parsed.sequence, 10);
+        assert_eq!(parsed.payload, b"payload".to_vec());
+
+        // Emitters other than the expected one are rejected.
+        assert!(matches!(
+            read_and_validate_vaa(&id, &vaa, Chain::Binance, &[4u8; 32]),
+            Err(InvalidEmitter)
+        ));
+        assert!(matches!(
+            read_and_validate_vaa(&id, &vaa, Chain::Ethereum, &[5u8; 32]),
+            Err(InvalidEmitter)
+        ));
+
+        // Accounts not owned by the bridge are rejected.
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = account_data(b"vaa", &message());
+        let vaa = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert!(matches!(
+            read_and_validate_vaa(&id, &vaa, Chain::Ethereum, &[4u8; 32]),
+            Err(InvalidAccount)
+        ));
+    }
+
+    #[test]
+    fn test_read_and_validate_vaa_rejects_messages_and_wrong_address() {
+        let id = Pubkey::new_unique();
+
+        // A posted message parses as a PostedVAAData but carries no guardian signatures.
+        let mut data = account_data(b"msg", &message());
+        let key = posted_vaa(&id, &posted_vaa_hash(&read_vaa_data(&data)));
+        let mut lamports = 0;
+        let vaa = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &id, false, 0);
+        assert!(matches!(
+            read_and_validate_vaa(&id, &vaa, Chain::Ethereum, &[4u8; 32]),
+            Err(InvalidAccount)
+        ));
+
+        // A VAA at an address not derived from its body could have been posted with another body.
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = account_data(b"vaa", &message());
+        let vaa = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &id, false, 0);
+        assert!(matches!(
+            read_and_validate_vaa(&id, &vaa, Chain::Ethereum, &[4u8; 32]),
+            Err(InvalidAccount)
+        ));
+    }
+
+    fn read_vaa_data(data: &[u8]) -> PostedVAAData {
+        PostedVAAData::try_from_slice(data).unwrap()
+    }
+
+    #[test]
+    fn test_consume_claim() {
+        setup();
+        let program_id = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let vaa = read_vaa_data(&account_data(b"vaa", &message()));
+        let (key, _) = claim(&program_id, &vaa);
+        let mut lamports = 0;
+        let mut data: Vec<u8> = vec![];
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &key, false, 0);
+
+        consume_claim(&program_id, &payer, &info, &vaa, &[info.clone()]).unwrap();
+        assert_eq!(info.data.borrow()[..], [1]);
+        let rent = Rent::default().minimum_balance(1);
+        INVOKED.with(|i| {
+            assert_eq!(
+                *i.borrow(),
+                vec![
+                    system_instruction::transfer(&payer, &key, rent),
+                    system_instruction::allocate(&key, 1),
+                    system_instruction::assign(&key, &program_id),
+                ]
+            )
+        });
+
+        // Once consumed, the claim can't be consumed again.
+        assert_eq!(
+            consume_claim(&program_id, &payer, &info, &vaa, &[info.clone()]).unwrap_err(),
+            ProgramError::AccountAlreadyInitialized
+        );
+        assert!(matches!(check_unclaimed(&info), Err(AlreadyClaimed)));
+    }
+
+    #[test]
+    fn test_consume_prefunded_claim() {
+        setup();
+        let program_id = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let vaa = read_vaa_data(&account_data(b"vaa", &message()));
+        let (key, _) = claim(&program_id, &vaa);
+
+        // Lamports sent to the claim address ahead of time must not block the claim, and only the
+        // shortfall is paid.
+        let rent = Rent::default().minimum_balance(1);
+        let mut lamports = rent - 1;
+        let mut data: Vec<u8> = vec![];
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &key, false, 0);
+        check_unclaimed(&info).unwrap();
+        consume_claim(&program_id, &payer, &info, &vaa, &[info.clone()]).unwrap();
+        INVOKED.with(|i| assert_eq!(i.borrow()[0], system_instruction::transfer(&payer, &key, 1)));
+
+        // A fully funded claim needs no transfer at all.
+        setup();
+        let mut lamports = rent * 2;
+        let mut data: Vec<u8> = vec![];
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &key, false, 0);
+        consume_claim(&program_id, &payer, &info, &vaa, &[info.clone()]).unwrap();
+        INVOKED.with(|i| {
+            assert_eq!(
+                *i.borrow(),
+                vec![
+                    system_instruction::allocate(&key, 1),
+                    system_instruction::assign(&key, &program_id),
+                ]
+            )
+        });
+
+        // The claim must live at the address derived from the VAA.
+        let other = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data: Vec<u8> = vec![];
+        let info = AccountInfo::new(
+            &other,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &other,
+            false,
+            0,
+        );
+        assert_eq!(
+            consume_claim(&program_id, &payer, &info, &vaa, &[info.clone()]).unwrap_err(),
+            ProgramError::InvalidSeeds
+        );
+    }
+}
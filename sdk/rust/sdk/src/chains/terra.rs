@@ -5,28 +5,45 @@ use serde::Serialize;
 
 use wormhole::msg::{ExecuteMsg, QueryMsg};
 use wormhole::state::ParsedVAA;
+use wormhole_core::{Chain, Network};
+
+/// Core Contract Address on the given network.
+pub fn id_for(network: Network) -> Addr {
+    Addr::unchecked(network.core(Chain::Terra).unwrap())
+}
 
 /// Export Core Mainnet Contract Address
 #[cfg(feature = "mainnet")]
 pub fn id() -> Addr {
-    Addr::unchecked("terra1dq03ugtd40zu9hcgdzrsq6z2z4hwhc9tqk2uy5")
+    id_for(Network::Mainnet)
 }
 
-/// Export Core Devnet Contract Address
+/// Export Core Testnet Contract Address
 #[cfg(feature = "testnet")]
 pub fn id() -> Addr {
-    Addr::unchecked("terra1pd65m0q9tl3v8znnz5f5ltsfegyzah7g42cx5v")
+    id_for(Network::Testnet)
 }
 
 /// Export Core Devnet Contract Address
 #[cfg(feature = "devnet")]
 pub fn id() -> Addr {
-    Addr::unchecked("terra18vd8fpwxzck93qlwghaj6arh4p7c5n896xzem5")
+    id_for(Network::Devnet)
 }
 
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
 pub fn post_message(nonce: u32, message: impl AsRef<[u8]>) -> StdResult<CosmosMsg> {
+    post_message_to(&id(), nonce, message)
+}
+
+/// Same as `post_message`, but posts to the contract at `wormhole` which allows the network to be
+/// chosen at runtime with `id_for`.
+pub fn post_message_to(
+    wormhole: &Addr,
+    nonce: u32,
+    message: impl AsRef<[u8]>,
+) -> StdResult<CosmosMsg> {
     Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: id().to_string(),
+        contract_addr: wormhole.to_string(),
         funds: vec![],
         msg: to_binary(&ExecuteMsg::PostMessage {
             message: Binary::from(message.as_ref()),
@@ -36,9 +53,20 @@ pub fn post_message(nonce: u32, message: impl AsRef<[u8]>) -> StdResult<CosmosMs
 }
 
 /// Parse a VAA using the Wormhole contract Query interface.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
 pub fn parse_vaa(deps: DepsMut, env: Env, data: &Binary) -> StdResult<ParsedVAA> {
+    parse_vaa_from(&id(), deps, env, data)
+}
+
+/// Same as `parse_vaa`, but queries the contract at `wormhole`.
+pub fn parse_vaa_from(
+    wormhole: &Addr,
+    deps: DepsMut,
+    env: Env,
+    data: &Binary,
+) -> StdResult<ParsedVAA> {
     let vaa: ParsedVAA = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: id().to_string(),
+        contract_addr: wormhole.to_string(),
         msg: to_binary(&QueryMsg::VerifyVAA {
             vaa: data.clone(),
             block_time: env.block.time.seconds(),
@@ -9,6 +9,8 @@
 //!
 //! Runtime   | Feature Flag            | Version
 //! ----------|-------------------------|----------------------------------------------------
+//! CosmWasm  | --feature=cosmwasm      | cosmwasm-std 1.0.0
+//! NEAR      | --feature=near          | near-sdk 4.1.1
 //! Solana    | --feature=solana        | solana-sdk 1.7.1
 //! Terra     | --feature=terra         | cosmos-sdk 0.16.0
 //!
@@ -17,5 +19,7 @@
 
 pub mod chains;
 
+// Empty unless one of the runtime features is enabled.
+#[allow(unused_imports)]
 pub use chains::*;
 pub use wormhole_core::*;
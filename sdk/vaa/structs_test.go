@@ -768,3 +768,50 @@ func TestIsTransfer(t *testing.T) {
 		})
 	}
 }
+
+// batchFixture is a batch VAA of two observations signed by the guardians with secret keys 1, 2
+// and 3. The Rust SDK parses and verifies the same bytes, see sdk/rust/core/src/vaa/batch.rs.
+const batchFixture = "02000000000300b4d299eb40c9d969ccf44bad83ff4c9c4b76f57868c5364b2a6d85f30beceaae519f184e36d0795195543fa191c79c5cb088bdd9d39712af4b44110a7fdf39d80101dd13a34001541c1dee784d4f26ca1ecb7b5506a044b6923d897cb3ba893db4a443a1dad50ccd41be10b482c50285d42ad8fdcae33f939352931a25cadc0637c4010229542af341f99a26c446dc6071a8b21801a8a06eeaedc654fc5c28cee4093ba73e1ff93ef54bab3bd81b11d23cc1544fc04c58db415fd4554abcbae0189969b20102b6862eca56ec6cac61caacc515f11ef2ea016dfd8bc7f1cd4a98fd7324641e904d29d79ccd7012f3ff1b4269dfb92174a9b1709381cce58c6b37f99d4337686f02000000004462b48e000000000700020000000000000000000000000000000000000000000000000000000000000004000000000000000a0f6669727374206f62736572766174696f6e010000004562b48e000000000700020000000000000000000000000000000000000000000000000000000000000004000000000000000b0f7365636f6e64206f62736572766174696f6e"
+
+func TestBatchVAAFixture(t *testing.T) {
+	observation := func(sequence uint64, payload string) *VAA {
+		return &VAA{
+			Version:          uint8(1),
+			Timestamp:        time.Unix(1656000000, 0),
+			Nonce:            uint32(7),
+			EmitterChain:     ChainIDEthereum,
+			EmitterAddress:   Address{31: 4},
+			Sequence:         sequence,
+			ConsistencyLevel: uint8(15),
+			Payload:          []byte(payload),
+		}
+	}
+
+	batch := &BatchVAA{
+		Version:          BatchVAAVersion,
+		GuardianSetIndex: uint32(0),
+		Observations: []*Observation{
+			{Index: 0, Observation: observation(10, "first observation")},
+			{Index: 1, Observation: observation(11, "second observation")},
+		},
+	}
+
+	addresses := []common.Address{}
+	for i := 1; i <= 3; i++ {
+		key, err := crypto.ToECDSA(common.LeftPadBytes([]byte{byte(i)}, 32))
+		require.NoError(t, err)
+		batch.AddSignature(key, uint8(i-1))
+		addresses = append(addresses, crypto.PubkeyToAddress(key.PublicKey))
+	}
+
+	marshalled, err := batch.Marshal()
+	require.NoError(t, err)
+	assert.Equal(t, batchFixture, hex.EncodeToString(marshalled))
+
+	fixture, err := hex.DecodeString(batchFixture)
+	require.NoError(t, err)
+	parsed, err := UnmarshalBatch(fixture)
+	require.NoError(t, err)
+	assert.True(t, parsed.VerifySignatures(addresses))
+	assert.Equal(t, batch.SigningMsg(), parsed.SigningMsg())
+}
@@ -9,9 +9,11 @@ use std::{
 
 use borsh::BorshDeserialize;
 use bridge::accounts::{
+    fee_schedule_key,
     Bridge,
     BridgeData,
     FeeCollector,
+    FeeScheduleData,
 };
 use clap::{
     crate_description,
@@ -130,7 +132,14 @@ fn command_post_message(
             None, bridge,
         ))?;
     let bridge_config = BridgeData::try_from_slice(bridge_config_account.data.as_slice())?;
-    let fee = bridge_config.config.fee;
+    let fee_schedule = match config.rpc_client.get_account(&fee_schedule_key(bridge)) {
+        Ok(account) => FeeScheduleData::try_from_slice(account.data.as_slice())?,
+        Err(_) => FeeScheduleData::default(),
+    };
+    let fee = fee_schedule
+        .message_fee(&bridge_config.config, &config.owner.pubkey())
+        .fee(payload.len())
+        .ok_or("message fee overflows")?;
     println!("Message fee: {} lamports", fee);
 
     let transfer_ix = transfer(
@@ -1,9 +1,11 @@
 pub mod bridge;
 pub mod claim;
 pub mod fee_collector;
+pub mod fee_schedule;
 pub mod guardian_set;
 pub mod posted_message;
 pub mod posted_vaa;
+pub mod rent_payer;
 pub mod sequence;
 pub mod signature_set;
 
@@ -11,9 +13,11 @@ pub use self::{
     bridge::*,
     claim::*,
     fee_collector::*,
+    fee_schedule::*,
     guardian_set::*,
     posted_message::*,
     posted_vaa::*,
+    rent_payer::*,
     sequence::*,
     signature_set::*,
 };
@@ -0,0 +1,169 @@
+//! The FeeSchedule holds the parts of the message fee that governance can set beyond the flat fee
+//! in the bridge config: a fee per byte of payload, and fee schedules for individual emitters that
+//! replace the bridge-wide one.
+//!
+//! The schedule lives in its own account rather than in the bridge config, because programs posting
+//! messages deserialize the bridge config with a fixed layout. PostMessage finds the schedule among
+//! the accounts passed after its own, so instructions built before fee schedules existed keep their
+//! layout and only need the schedule appended.
+//!
+//! Messages posted without the schedule are charged the flat fee, so programs that were not
+//! upgraded keep posting. Only the per byte fee of callers passing the schedule can be enforced
+//! this way. An emitter that must pay its fee is given an override, which marks its sequence
+//! tracker so that its messages are rejected without the schedule.
+
+use crate::{
+    error::Error::MathOverflow,
+    BridgeConfig,
+};
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::AccountMeta,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+    Result,
+    SolitaireError,
+};
+
+pub type FeeSchedule<'a, const State: AccountState> =
+    Derive<Data<'a, FeeScheduleData, { State }>, "FeeSchedule">;
+
+#[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct FeeScheduleData {
+    /// Amount of lamports that needs to be paid per byte of message payload, on top of the flat fee
+    /// in the bridge config.
+    pub fee_per_byte: u64,
+
+    /// Fee schedules replacing the bridge-wide one for messages from individual emitters.
+    pub overrides: Vec<EmitterMessageFee>,
+}
+
+#[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct EmitterMessageFee {
+    pub emitter: Pubkey,
+    pub fee: MessageFee,
+}
+
+/// Fee charged for posting a message, a base fee plus a fee per byte of payload.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct MessageFee {
+    /// Lamports charged for every message.
+    pub base: u64,
+
+    /// Lamports charged per byte of payload.
+    pub per_byte: u64,
+}
+
+impl MessageFee {
+    /// Fee for a message with a payload of `payload_len` bytes, `None` on overflow.
+    pub fn fee(&self, payload_len: usize) -> Option<u64> {
+        self.per_byte
+            .checked_mul(payload_len as u64)?
+            .checked_add(self.base)
+    }
+}
+
+impl FeeScheduleData {
+    /// Fee schedule for messages from `emitter`.
+    pub fn message_fee(&self, config: &BridgeConfig, emitter: &Pubkey) -> MessageFee {
+        self.overrides
+            .iter()
+            .find(|o| o.emitter == *emitter)
+            .map(|o| o.fee)
+            .unwrap_or(MessageFee {
+                base: config.fee,
+                per_byte: self.fee_per_byte,
+            })
+    }
+
+    /// Set the fee schedule for messages from `emitter`.
+    pub fn set_override(&mut self, emitter: Pubkey, fee: MessageFee) {
+        match self.overrides.iter_mut().find(|o| o.emitter == emitter) {
+            Some(o) => o.fee = fee,
+            None => self.overrides.push(EmitterMessageFee { emitter, fee }),
+        }
+    }
+
+    /// Find the fee schedule of the bridge at `bridge` among `accounts`. Returns `None` if it was
+    /// not passed, and the default schedule if governance has not created it yet.
+    pub fn from_accounts(bridge: &Pubkey, accounts: &[AccountInfo]) -> Result<Option<Self>> {
+        let key = fee_schedule_key(bridge);
+        let info = match accounts.iter().find(|a| *a.key == key) {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+        if info.data_is_empty() {
+            return Ok(Some(FeeScheduleData::default()));
+        }
+        if info.owner != bridge {
+            return Err(SolitaireError::InvalidOwner(*info.owner));
+        }
+        Ok(Some(FeeScheduleData::try_from_slice(&info.data.borrow())?))
+    }
+}
+
+/// Address of the fee schedule of the bridge at `bridge`.
+pub fn fee_schedule_key(bridge: &Pubkey) -> Pubkey {
+    FeeSchedule::<'_, { AccountState::Uninitialized }>::key(None, bridge)
+}
+
+/// Fee the bridge at `bridge` charges for a message from `emitter` with a `payload_len` byte
+/// payload, for programs that pay the fee before posting through a cross-program invocation. The
+/// fee schedule is looked up among `accounts`, without it only the flat fee is charged as bridges
+/// deployed before fee schedules do.
+pub fn message_fee(
+    bridge: &Pubkey,
+    config: &BridgeConfig,
+    emitter: &Pubkey,
+    accounts: &[AccountInfo],
+    payload_len: usize,
+) -> Result<u64> {
+    let fee = FeeScheduleData::from_accounts(bridge, accounts)?
+        .unwrap_or_default()
+        .message_fee(config, emitter)
+        .fee(payload_len)
+        .ok_or(MathOverflow)?;
+    Ok(fee)
+}
+
+/// Account meta for the fee schedule to append to a cross-program `post_message` invocation, if
+/// the fee schedule was passed among `accounts`.
+pub fn fee_schedule_meta(bridge: &Pubkey, accounts: &[AccountInfo]) -> Option<AccountMeta> {
+    let key = fee_schedule_key(bridge);
+    accounts
+        .iter()
+        .any(|a| *a.key == key)
+        .then(|| AccountMeta::new_readonly(key, false))
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for FeeScheduleData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for FeeScheduleData {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
+    }
+}
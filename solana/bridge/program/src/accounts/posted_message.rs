@@ -53,7 +53,8 @@ pub struct MessageData {
     /// Time the vaa was submitted
     pub vaa_time: u32,
 
-    /// Account where signatures are stored
+    /// Account where signatures are stored, the default pubkey for VAAs posted with their
+    /// signatures
     pub vaa_signature_account: Pubkey,
 
     /// Time the posted message was created
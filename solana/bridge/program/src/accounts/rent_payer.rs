@@ -0,0 +1,99 @@
+//! RentPayer records who paid for an account created by the bridge, so that the rent can be
+//! returned to them when the account is closed.
+//!
+//! Signature sets store the record in an optional trailer, see `signature_set`.
+//!
+//! Posted VAAs are read with a fixed layout by the programs consuming them, so their record is kept
+//! in a separate PostedVAAPayer account derived from the posted VAA instead. PostVAA finds it among
+//! the accounts passed after its own, so instructions built before payers were recorded keep their
+//! layout. VAAs posted without it have no payer and can never be closed. The record also holds the
+//! time the VAA was posted, as a VAA can only be closed once it is old enough.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+
+pub type PostedVAAPayer<'b, const State: AccountState> = Data<'b, PostedVAAPayerData, { State }>;
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct RentPayer {
+    /// Account that paid the rent, and the only one that can close the account to get it back.
+    pub payer: Pubkey,
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct PostedVAAPayerData {
+    /// Account that paid the rent of the posted VAA, and the only one that can close it.
+    pub payer: Pubkey,
+
+    /// Time the VAA was posted.
+    pub post_time: u32,
+}
+
+impl Owned for PostedVAAPayerData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// PostedVAAPayer account PDAs are indexed by the posted VAA they record the payer of.
+pub struct PostedVAAPayerDerivationData {
+    pub posted_vaa: Pubkey,
+}
+
+impl<'a, const State: AccountState> Seeded<&PostedVAAPayerDerivationData>
+    for PostedVAAPayer<'a, { State }>
+{
+    fn seeds(data: &PostedVAAPayerDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            "PostedVAAPayer".as_bytes().to_vec(),
+            data.posted_vaa.to_bytes().to_vec(),
+        ]
+    }
+}
+
+/// Address of the account recording the payer of the posted VAA at `posted_vaa`.
+pub fn posted_vaa_payer_key(bridge: &Pubkey, posted_vaa: &Pubkey) -> Pubkey {
+    PostedVAAPayer::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAAPayerDerivationData {
+            posted_vaa: *posted_vaa,
+        },
+        bridge,
+    )
+}
+
+/// Find the account recording the payer of the posted VAA at `posted_vaa` among `accounts`.
+pub fn find_posted_vaa_payer<'a, 'b>(
+    bridge: &Pubkey,
+    posted_vaa: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Option<&'a AccountInfo<'b>> {
+    let key = posted_vaa_payer_key(bridge, posted_vaa);
+    accounts.iter().find(|a| *a.key == key)
+}
+
+impl Owned for RentPayer {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -10,19 +10,51 @@ use solitaire::{
     Data,
     Owned,
 };
+use std::io::Write;
 
 pub type Sequence<'b> = Data<'b, SequenceTracker, { AccountState::MaybeInitialized }>;
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize)]
+#[derive(Default, Clone, Copy)]
 pub struct SequenceTracker {
     pub sequence: u64,
+
+    /// Whether messages from the emitter must pass the fee schedule, set once governance gives the
+    /// emitter its own message fee.
+    pub fee_schedule_required: bool,
+}
+
+// Clients read the sequence with a fixed layout, so the flag is kept in a trailer that is only
+// written when set and only read when bytes remain.
+impl BorshSerialize for SequenceTracker {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.sequence, writer)?;
+        if self.fee_schedule_required {
+            BorshSerialize::serialize(&self.fee_schedule_required, writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for SequenceTracker {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let sequence = BorshDeserialize::deserialize(buf)?;
+        let fee_schedule_required = match buf.is_empty() {
+            true => false,
+            false => BorshDeserialize::deserialize(buf)?,
+        };
+
+        Ok(SequenceTracker {
+            sequence,
+            fee_schedule_required,
+        })
+    }
 }
 
 pub struct SequenceDerivationData<'a> {
     pub emitter_key: &'a Pubkey,
 }
 
-impl<'b> Seeded<&SequenceDerivationData<'b>> for Sequence<'b> {
+impl<'a, 'b> Seeded<&SequenceDerivationData<'a>> for Sequence<'b> {
     fn seeds(data: &SequenceDerivationData) -> Vec<Vec<u8>> {
         vec![
             "Sequence".as_bytes().to_vec(),
@@ -1,5 +1,6 @@
 //! PostedMessage
 
+use crate::RentPayer;
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -10,10 +11,11 @@ use solitaire::{
     Data,
     Owned,
 };
+use std::io::Write;
 
 pub type SignatureSet<'b, const State: AccountState> = Data<'b, SignatureSetData, { State }>;
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+#[derive(Default)]
 pub struct SignatureSetData {
     /// Signatures of validators
     pub signatures: Vec<bool>,
@@ -23,6 +25,48 @@ pub struct SignatureSetData {
 
     /// Index of the guardian set
     pub guardian_set_index: u32,
+
+    /// Payer of the account rent, `None` for signature sets created before payers were recorded.
+    pub payer: Option<RentPayer>,
+
+    /// Whether governance blocked the signature set from posting VAAs.
+    pub blocked: bool,
+}
+
+// Signature sets created before payers were recorded end after the guardian set index. The payer
+// and block are kept in a trailer after it, which is only written when either is set and only read
+// when bytes remain, so those signature sets still deserialize.
+impl BorshSerialize for SignatureSetData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.signatures, writer)?;
+        BorshSerialize::serialize(&self.hash, writer)?;
+        BorshSerialize::serialize(&self.guardian_set_index, writer)?;
+        if self.payer.is_some() || self.blocked {
+            BorshSerialize::serialize(&self.payer, writer)?;
+            BorshSerialize::serialize(&self.blocked, writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for SignatureSetData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let signatures = BorshDeserialize::deserialize(buf)?;
+        let hash = BorshDeserialize::deserialize(buf)?;
+        let guardian_set_index = BorshDeserialize::deserialize(buf)?;
+        let (payer, blocked) = match buf.is_empty() {
+            true => (None, false),
+            false => BorshDeserialize::deserialize(buf)?,
+        };
+
+        Ok(SignatureSetData {
+            signatures,
+            hash,
+            guardian_set_index,
+            payer,
+            blocked,
+        })
+    }
 }
 
 impl Owned for SignatureSetData {
@@ -0,0 +1,224 @@
+//! Close the SignatureSet and PostedVAA accounts created while posting VAA's, returning their rent.
+//!
+//! Only the payer recorded for an account can close it, and the rent is always returned to them.
+//! Signature sets created before payers were recorded can only be closed by signing with their own
+//! keypair, and VAAs posted without a PostedVAAPayer account cannot be closed at all.
+//!
+//! A closed PostedVAA is not returned to the system program. It is left behind as an empty account
+//! owned by the bridge, funded just enough to be rent exempt, which PostVAA refuses to post over. A
+//! closed VAA can therefore never be posted again, even though the claims consumers use for replay
+//! protection would also reject it. As anyone can post a VAA, a VAA can only be closed once it is
+//! `POSTED_VAA_CLOSE_AGE` old, so that whoever posted it cannot keep the programs consuming it from
+//! ever redeeming it. Both instructions check the accounts they close are the type
+//! they expect, so they cannot be used to close a `Claim` or any other account owned by the bridge.
+
+use solitaire::*;
+
+use crate::{
+    accounts::{
+        GuardianSet,
+        GuardianSetDerivationData,
+        PostedVAA,
+        PostedVAAData,
+        PostedVAADerivationData,
+        PostedVAAPayer,
+        PostedVAAPayerDerivationData,
+        SignatureSet,
+    },
+    api::post_vaa::{
+        vaa_body_hash,
+        PostVAAData,
+    },
+    error::Error::{
+        GuardianSetMismatch,
+        InvalidCloseAuthority,
+        MathOverflow,
+        VAAInvalid,
+        VAATooRecent,
+    },
+    events::BridgeEvent,
+};
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    system_program,
+    sysvar::{
+        clock::Clock,
+        rent::Rent,
+        Sysvar as _,
+    },
+};
+use solitaire::processors::seeded::Seeded;
+
+/// Seconds after being posted before a VAA can be closed, 30 days.
+pub const POSTED_VAA_CLOSE_AGE: u32 = 30 * 24 * 60 * 60;
+
+#[derive(FromAccounts)]
+pub struct CloseSignatureSet<'b> {
+    /// Payer of the signature set, receiving its rent.
+    pub authority: Mut<Signer<Info<'b>>>,
+
+    /// Guardian set of the signatures.
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Signature set to close.
+    pub signature_set: Mut<SignatureSet<'b, { AccountState::Initialized }>>,
+}
+
+impl From<&CloseSignatureSet<'_>> for GuardianSetDerivationData {
+    fn from(data: &CloseSignatureSet<'_>) -> Self {
+        GuardianSetDerivationData {
+            index: data.signature_set.guardian_set_index,
+        }
+    }
+}
+
+#[derive(Default, BorshSerialize, BorshDeserialize)]
+pub struct CloseSignatureSetData {}
+
+pub fn close_signature_set(
+    ctx: &ExecutionContext,
+    accs: &mut CloseSignatureSet,
+    _data: CloseSignatureSetData,
+) -> Result<()> {
+    accs.guardian_set
+        .verify_derivation(ctx.program_id, &(&*accs).into())?;
+
+    // Signature sets are not derived, so check the account really is one by making sure it holds
+    // a signature for each guardian in its guardian set.
+    if accs.signature_set.signatures.len() != accs.guardian_set.keys.len() {
+        return Err(GuardianSetMismatch.into());
+    }
+
+    let authorized = match accs.signature_set.payer {
+        Some(payer) => payer.payer == *accs.authority.key,
+
+        // Legacy signature sets don't record their payer, only the keypair that created the
+        // signature set can close it.
+        None => accs.signature_set.info().is_signer,
+    };
+    if !authorized {
+        return Err(InvalidCloseAuthority.into());
+    }
+
+    close_account(accs.signature_set.info(), &accs.authority)?;
+
+    BridgeEvent::SignatureSetClosed {
+        signature_set: *accs.signature_set.info().key,
+        recipient: *accs.authority.key,
+    }
+    .emit()
+}
+
+#[derive(FromAccounts)]
+pub struct ClosePostedVAA<'b> {
+    /// Payer of the VAA, receiving its rent.
+    pub authority: Mut<Signer<Info<'b>>>,
+
+    /// Posted VAA to close. Read by hand, as the account is left empty once closed.
+    pub posted_vaa: Mut<Info<'b>>,
+
+    /// Record of the payer of the VAA, closed along with it.
+    pub posted_vaa_payer: Mut<PostedVAAPayer<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(Default, BorshSerialize, BorshDeserialize)]
+pub struct ClosePostedVAAData {}
+
+pub fn close_posted_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut ClosePostedVAA,
+    _data: ClosePostedVAAData,
+) -> Result<()> {
+    // PostedVAAData also deserializes posted messages, only accept accounts holding a VAA.
+    if accs.posted_vaa.owner != ctx.program_id || !accs.posted_vaa.data.borrow().starts_with(b"vaa")
+    {
+        return Err(VAAInvalid.into());
+    }
+    let posted_vaa = PostedVAAData::try_from_slice(&accs.posted_vaa.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    // Derive the account from the VAA it holds, so that it can only be the account the VAA was
+    // posted to.
+    let body_hash = vaa_body_hash(&PostVAAData {
+        timestamp: posted_vaa.vaa_time,
+        nonce: posted_vaa.nonce,
+        emitter_chain: posted_vaa.emitter_chain,
+        emitter_address: posted_vaa.emitter_address,
+        sequence: posted_vaa.sequence,
+        consistency_level: posted_vaa.consistency_level,
+        payload: posted_vaa.payload.clone(),
+        ..Default::default()
+    })?;
+    let key = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body_hash.to_vec(),
+        },
+        ctx.program_id,
+    );
+    if key != *accs.posted_vaa.key {
+        return Err(VAAInvalid.into());
+    }
+
+    accs.posted_vaa_payer.verify_derivation(
+        ctx.program_id,
+        &PostedVAAPayerDerivationData {
+            posted_vaa: *accs.posted_vaa.key,
+        },
+    )?;
+
+    // VAAs posted without a payer record cannot be closed by anyone.
+    if !accs.posted_vaa_payer.is_initialized() || accs.posted_vaa_payer.payer != *accs.authority.key
+    {
+        return Err(InvalidCloseAuthority.into());
+    }
+
+    let closeable_time = accs.posted_vaa_payer.post_time as i64 + POSTED_VAA_CLOSE_AGE as i64;
+    if Clock::get()?.unix_timestamp < closeable_time {
+        return Err(VAATooRecent.into());
+    }
+
+    close_account(accs.posted_vaa_payer.info(), &accs.authority)?;
+
+    // Leave the VAA behind as an empty account, keeping only the lamports it needs to stay rent
+    // exempt, so that PostVAA can tell it was closed.
+    let remaining = Rent::get()?.minimum_balance(0);
+    let lamports = accs
+        .posted_vaa
+        .lamports()
+        .checked_sub(remaining)
+        .ok_or(MathOverflow)?;
+    move_lamports(&accs.posted_vaa, &accs.authority, lamports)?;
+    accs.posted_vaa.data.borrow_mut().fill(0);
+    accs.posted_vaa.realloc(0, false)?;
+
+    BridgeEvent::PostedVAAClosed {
+        posted_vaa: *accs.posted_vaa.key,
+        recipient: *accs.authority.key,
+    }
+    .emit()
+}
+
+/// Move all lamports of an account owned by this program to `recipient`. The data is zeroed and
+/// the account handed back to the system program, so that it cannot be revived with its old
+/// contents by refunding it later in the same transaction.
+fn close_account(account: &AccountInfo, recipient: &AccountInfo) -> Result<()> {
+    move_lamports(account, recipient, account.lamports())?;
+    account.data.borrow_mut().fill(0);
+    account.assign(&system_program::id());
+
+    Ok(())
+}
+
+fn move_lamports(account: &AccountInfo, recipient: &AccountInfo, lamports: u64) -> Result<()> {
+    let balance = recipient
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(MathOverflow)?;
+
+    **recipient.lamports.borrow_mut() = balance;
+    **account.lamports.borrow_mut() -= lamports;
+
+    Ok(())
+}
@@ -1,5 +1,9 @@
 use solana_program::{
-    program::invoke_signed,
+    account_info::AccountInfo,
+    program::{
+        invoke,
+        invoke_signed,
+    },
     pubkey::Pubkey,
     sysvar::{
         clock::Clock,
@@ -19,18 +23,30 @@ use crate::{
             Claim,
         },
         Bridge,
+        FeeSchedule,
         GuardianSet,
         GuardianSetDerivationData,
+        MessageFee,
+        Sequence,
+        SequenceDerivationData,
+        SignatureSet,
     },
     error::Error::{
         InvalidFeeRecipient,
         InvalidGovernanceKey,
         InvalidGovernanceWithdrawal,
+        InvalidGuardianSetExpiration,
         InvalidGuardianSetUpgrade,
+        SignatureSetMismatch,
     },
+    events::BridgeEvent,
     types::{
+        GovernancePayloadBlockSignatureSet,
+        GovernancePayloadExpireGuardianSet,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetEmitterMessageFee,
         GovernancePayloadSetMessageFee,
+        GovernancePayloadSetMessageFeeSchedule,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
     },
@@ -114,7 +130,10 @@ pub fn upgrade_contract(
     let seeds = seeds.as_slice();
     invoke_signed(&upgrade_ix, ctx.accounts, &[seeds])?;
 
-    Ok(())
+    BridgeEvent::ContractUpgraded {
+        new_contract: accs.vaa.new_contract,
+    }
+    .emit()
 }
 
 #[derive(FromAccounts)]
@@ -195,7 +214,13 @@ pub fn upgrade_guardian_set(
     // Set guardian set index
     accs.bridge.guardian_set_index = accs.vaa.new_guardian_set_index;
 
-    Ok(())
+    BridgeEvent::GuardianSetUpgraded {
+        old_guardian_set_index: accs.guardian_set_old.index,
+        old_guardian_set_expiration_time: accs.guardian_set_old.expiration_time,
+        new_guardian_set_index: accs.guardian_set_new.index,
+        new_guardian_set: accs.guardian_set_new.keys.clone(),
+    }
+    .emit()
 }
 
 #[derive(FromAccounts)]
@@ -220,7 +245,12 @@ pub fn set_fees(ctx: &ExecutionContext, accs: &mut SetFees, _data: SetFeesData)
     verify_governance(&accs.vaa)?;
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
     accs.bridge.config.fee = accs.vaa.fee.as_u64();
-    Ok(())
+
+    BridgeEvent::MessageFeeChanged {
+        fee: accs.bridge.config.fee,
+        fee_per_byte: None,
+    }
+    .emit()
 }
 
 #[derive(FromAccounts)]
@@ -285,5 +315,283 @@ pub fn transfer_fees(
     let seeds = seeds.as_slice();
     invoke_signed(&transfer_ix, ctx.accounts, &[seeds])?;
 
+    BridgeEvent::FeesTransferred {
+        recipient: *accs.recipient.key,
+        amount: accs.vaa.amount.as_u64(),
+    }
+    .emit()
+}
+
+#[derive(FromAccounts)]
+pub struct ExpireGuardianSet<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadExpireGuardianSet>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Guardian set to expire
+    pub guardian_set: Mut<GuardianSet<'b, { AccountState::Initialized }>>,
+
+    /// Clock used to expire the guardian set.
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ExpireGuardianSetData {}
+
+pub fn expire_guardian_set(
+    ctx: &ExecutionContext,
+    accs: &mut ExpireGuardianSet,
+    _data: ExpireGuardianSetData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.guardian_set.verify_derivation(
+        ctx.program_id,
+        &GuardianSetDerivationData {
+            index: accs.vaa.guardian_set_index,
+        },
+    )?;
+
+    // The active guardian set is needed to sign the governance VAA replacing it, so expiring it
+    // would halt the bridge.
+    if accs.guardian_set.index == accs.bridge.guardian_set_index {
+        return Err(InvalidGuardianSetExpiration.into());
+    }
+
+    // Expire the set as of the previous second, as sets are valid up to and including their
+    // expiration time. Sets that already expired earlier are left untouched.
+    let expiration_time = (accs.clock.unix_timestamp - 1) as u32;
+    if accs.guardian_set.expiration_time == 0 || accs.guardian_set.expiration_time > expiration_time
+    {
+        accs.guardian_set.expiration_time = expiration_time;
+    }
+
+    BridgeEvent::GuardianSetExpired {
+        guardian_set_index: accs.guardian_set.index,
+        expiration_time: accs.guardian_set.expiration_time,
+    }
+    .emit()
+}
+
+#[derive(FromAccounts)]
+pub struct BlockSignatureSet<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadBlockSignatureSet>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Signature set to block
+    pub signature_set: Mut<SignatureSet<'b, { AccountState::Initialized }>>,
+
+    /// Rent calculator to fund the larger signature set.
+    pub rent: Sysvar<'b, Rent>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct BlockSignatureSetData {}
+
+pub fn block_signature_set(
+    ctx: &ExecutionContext,
+    accs: &mut BlockSignatureSet,
+    _data: BlockSignatureSetData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if *accs.signature_set.info().key != accs.vaa.signature_set {
+        return Err(SignatureSetMismatch.into());
+    }
+
+    // Signature sets without a trailer grow to hold the block.
+    accs.signature_set.blocked = true;
+    let size = accs.signature_set.size();
+    resize_account(
+        ctx,
+        accs.signature_set.info(),
+        accs.payer.key,
+        &accs.rent,
+        size,
+    )?;
+
+    BridgeEvent::SignatureSetBlocked {
+        signature_set: accs.vaa.signature_set,
+    }
+    .emit()
+}
+
+#[derive(FromAccounts)]
+pub struct SetMessageFeeSchedule<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Fee schedule, created by the first fee schedule change.
+    pub fee_schedule: Mut<FeeSchedule<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetMessageFeeSchedule>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetMessageFeeScheduleData {}
+
+pub fn set_message_fee_schedule(
+    ctx: &ExecutionContext,
+    accs: &mut SetMessageFeeSchedule,
+    _data: SetMessageFeeScheduleData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.bridge.config.fee = accs.vaa.fee.as_u64();
+    accs.fee_schedule.fee_per_byte = accs.vaa.fee_per_byte.as_u64();
+    create_fee_schedule(ctx, &accs.fee_schedule, accs.payer.key)?;
+
+    BridgeEvent::MessageFeeChanged {
+        fee: accs.vaa.fee.as_u64(),
+        fee_per_byte: Some(accs.vaa.fee_per_byte.as_u64()),
+    }
+    .emit()
+}
+
+#[derive(FromAccounts)]
+pub struct SetEmitterMessageFee<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Fee schedule holding the emitter fee overrides, created by the first fee schedule change.
+    pub fee_schedule: Mut<FeeSchedule<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetEmitterMessageFee>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Sequence tracker of the emitter, marked so that its messages must pass the fee schedule.
+    pub sequence: Mut<Sequence<'b>>,
+
+    /// Rent calculator to fund a larger fee schedule.
+    pub rent: Sysvar<'b, Rent>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetEmitterMessageFeeData {}
+
+pub fn set_emitter_message_fee(
+    ctx: &ExecutionContext,
+    accs: &mut SetEmitterMessageFee,
+    _data: SetEmitterMessageFeeData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let fee = MessageFee {
+        base: accs.vaa.fee.as_u64(),
+        per_byte: accs.vaa.fee_per_byte.as_u64(),
+    };
+    accs.fee_schedule.set_override(accs.vaa.emitter, fee);
+    if accs.fee_schedule.is_initialized() {
+        // Each new override grows the fee schedule.
+        let size = accs.fee_schedule.size();
+        resize_account(
+            ctx,
+            accs.fee_schedule.info(),
+            accs.payer.key,
+            &accs.rent,
+            size,
+        )?;
+    }
+    create_fee_schedule(ctx, &accs.fee_schedule, accs.payer.key)?;
+
+    // Messages posted without the fee schedule would otherwise pay the flat fee instead.
+    let sequence_derivation = SequenceDerivationData {
+        emitter_key: &accs.vaa.emitter,
+    };
+    accs.sequence
+        .verify_derivation(ctx.program_id, &sequence_derivation)?;
+    accs.sequence.fee_schedule_required = true;
+    if accs.sequence.is_initialized() {
+        let size = accs.sequence.size();
+        resize_account(ctx, accs.sequence.info(), accs.payer.key, &accs.rent, size)?;
+    } else {
+        accs.sequence
+            .create(&sequence_derivation, ctx, accs.payer.key, Exempt)?;
+    }
+
+    BridgeEvent::EmitterMessageFeeChanged {
+        emitter: accs.vaa.emitter,
+        fee: fee.base,
+        fee_per_byte: fee.per_byte,
+    }
+    .emit()
+}
+
+/// Create the fee schedule account if this is the first change to the fee schedule.
+fn create_fee_schedule(
+    ctx: &ExecutionContext,
+    fee_schedule: &FeeSchedule<'_, { AccountState::MaybeInitialized }>,
+    payer: &Pubkey,
+) -> Result<()> {
+    if fee_schedule.is_initialized() {
+        return Ok(());
+    }
+
+    let seeds =
+        FeeSchedule::<'_, { AccountState::MaybeInitialized }>::bumped_seeds(None, ctx.program_id);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|seed| seed.as_slice()).collect();
+    create_account(
+        ctx,
+        fee_schedule.info(),
+        payer,
+        Exempt,
+        fee_schedule.size(),
+        ctx.program_id,
+        IsSigned::SignedWithSeeds(&[&seeds]),
+    )
+}
+
+/// Grow an account owned by this program to `size` bytes, topping up its lamports from `payer` so
+/// that it stays rent exempt.
+fn resize_account(
+    ctx: &ExecutionContext,
+    account: &AccountInfo,
+    payer: &Pubkey,
+    rent: &Rent,
+    size: usize,
+) -> Result<()> {
+    if account.data_len() >= size {
+        return Ok(());
+    }
+
+    let required = rent
+        .minimum_balance(size)
+        .saturating_sub(account.lamports());
+    if required > 0 {
+        let transfer_ix =
+            solana_program::system_instruction::transfer(payer, account.key, required);
+        invoke(&transfer_ix, ctx.accounts)?;
+    }
+
+    account.realloc(size, false)?;
+
     Ok(())
 }
@@ -7,6 +7,7 @@ use crate::{
         GuardianSetDerivationData,
     },
     error::Error::TooManyGuardians,
+    events::BridgeEvent,
     MAX_LEN_GUARDIAN_KEYS,
 };
 use solana_program::sysvar::clock::Clock;
@@ -91,5 +92,11 @@ pub fn initialize(
     )?;
     accs.bridge.last_lamports = accs.fee_collector.lamports();
 
-    Ok(())
+    BridgeEvent::Initialized {
+        guardian_set_index: index,
+        guardian_set: data.initial_guardians,
+        guardian_set_expiration_time: data.guardian_set_expiration_time,
+        fee: data.fee,
+    }
+    .emit()
 }
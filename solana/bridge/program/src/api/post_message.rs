@@ -2,6 +2,8 @@ use crate::{
     accounts::{
         Bridge,
         FeeCollector,
+        FeeScheduleData,
+        MessageFee,
         PostedMessage,
         PostedMessageUnreliable,
         Sequence,
@@ -12,7 +14,9 @@ use crate::{
         InsufficientFees,
         InvalidPayloadLength,
         MathOverflow,
+        MissingFeeSchedule,
     },
+    events::BridgeEvent,
     types::ConsistencyLevel,
     IsSigned::*,
     MessageData,
@@ -178,7 +182,7 @@ pub fn post_message_unreliable(
 fn post_message_internal<'b>(
     ctx: &ExecutionContext,
     bridge: &mut Mut<Bridge<'b, { AccountState::Initialized }>>,
-    #[cfg_attr(not(feature = "trace"), allow(unused_variables))] message_key: &Pubkey,
+    message_key: &Pubkey,
     message: &mut MessageData,
     emitter: &mut Signer<MaybeMut<Info<'b>>>,
     sequence: &mut Mut<Sequence<'b>>,
@@ -196,7 +200,18 @@ fn post_message_internal<'b>(
     };
     sequence.verify_derivation(ctx.program_id, &sequence_derivation)?;
 
-    let fee = bridge.config.fee;
+    // The fee schedule is passed after the accounts above so that PostMessage keeps the layout it
+    // had before fee schedules, see `accounts::fee_schedule` for callers that do not pass it.
+    let fee = match FeeScheduleData::from_accounts(ctx.program_id, ctx.accounts)? {
+        Some(fee_schedule) => fee_schedule.message_fee(&bridge.config, emitter.key),
+        None if sequence.fee_schedule_required => return Err(MissingFeeSchedule.into()),
+        None => MessageFee {
+            base: bridge.config.fee,
+            per_byte: 0,
+        },
+    }
+    .fee(data.payload.len())
+    .ok_or(MathOverflow)?;
     // Fee handling, checking previously known balance allows us to not care who is the payer of
     // this submission.
     if fee_collector
@@ -237,6 +252,17 @@ fn post_message_internal<'b>(
         ConsistencyLevel::Finalized => 32,
     };
 
+    BridgeEvent::MessagePosted {
+        message: *message_key,
+        emitter: *emitter.key,
+        sequence: message.sequence,
+        nonce: message.nonce,
+        consistency_level: message.consistency_level,
+        payload_len: message.payload.len() as u32,
+        fee,
+    }
+    .emit()?;
+
     // Bump sequence number
     trace!("New Sequence: {}", sequence.sequence + 1);
     sequence.sequence += 1;
@@ -6,24 +6,32 @@ use borsh::{
 };
 use solana_program::{
     self,
+    account_info::AccountInfo,
+    pubkey::Pubkey,
     sysvar::clock::Clock,
 };
 
 use crate::{
     accounts::{
+        find_posted_vaa_payer,
         Bridge,
         GuardianSet,
         GuardianSetDerivationData,
         PostedVAA,
         PostedVAADerivationData,
+        PostedVAAPayer,
+        PostedVAAPayerData,
+        PostedVAAPayerDerivationData,
         SignatureSet,
     },
     error::Error::{
         GuardianSetMismatch,
         PostVAAConsensusFailed,
         PostVAAGuardianSetExpired,
+        VAAClosed,
         VAAInvalid,
     },
+    events::BridgeEvent,
 };
 use byteorder::{
     BigEndian,
@@ -38,6 +46,7 @@ use solana_program::program_error::ProgramError;
 use solitaire::{
     processors::seeded::Seeded,
     CreationLamports::Exempt,
+    IsSigned,
 };
 use std::io::{
     Cursor,
@@ -109,6 +118,7 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
     accs.guardian_set
         .verify_derivation(ctx.program_id, &(&vaa).into())?;
 
+    check_not_closed(ctx, accs.message.info())?;
     if accs.message.is_initialized() {
         return Ok(());
     }
@@ -121,17 +131,7 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
     // Count the number of signatures currently present.
     let signature_count: usize = accs.signature_set.signatures.iter().filter(|v| **v).count();
 
-    // Calculate how many signatures are required to reach consensus. This calculation is in
-    // expanded form to ease auditing.
-    let required_consensus_count = {
-        let len = accs.guardian_set.keys.len();
-        // Fixed point number transformation with one decimal to deal with rounding.
-        let len = (len * 10) / 3;
-        // Multiplication by two to get a 2/3 quorum.
-        let len = len * 2;
-        // Division to bring number back into range.
-        len / 10 + 1
-    };
+    let required_consensus_count = required_consensus_count(accs.guardian_set.keys.len());
 
     if signature_count < required_consensus_count {
         return Err(PostVAAConsensusFailed.into());
@@ -149,13 +149,86 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
     accs.message.vaa_signature_account = *accs.signature_set.info().key;
     accs.message
         .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
+    record_payer(ctx, accs.message.info(), accs.payer.key, &accs.clock)?;
+
+    BridgeEvent::VAAPosted {
+        posted_vaa: *accs.message.info().key,
+        guardian_set_index: accs.guardian_set.index,
+        emitter_chain: accs.message.emitter_chain,
+        emitter_address: accs.message.emitter_address,
+        sequence: accs.message.sequence,
+    }
+    .emit()
+}
 
+/// Calculate how many signatures are required to reach consensus. This calculation is in expanded
+/// form to ease auditing.
+#[inline(always)]
+pub(crate) fn required_consensus_count(guardian_set_len: usize) -> usize {
+    let len = guardian_set_len;
+    // Fixed point number transformation with one decimal to deal with rounding.
+    let len = (len * 10) / 3;
+    // Multiplication by two to get a 2/3 quorum.
+    let len = len * 2;
+    // Division to bring number back into range.
+    len / 10 + 1
+}
+
+/// A VAA that was closed is left as an empty account owned by the bridge, and must not be posted
+/// again.
+#[inline(always)]
+pub(crate) fn check_not_closed(ctx: &ExecutionContext, message: &AccountInfo) -> Result<()> {
+    if message.owner == ctx.program_id && message.data_is_empty() {
+        return Err(VAAClosed.into());
+    }
     Ok(())
 }
 
-/// A guardian set must not have expired.
+/// Record the payer of a newly posted VAA and when it was posted, so that they can close it later,
+/// if the PostedVAAPayer account was passed after the accounts of the instruction.
+pub(crate) fn record_payer(
+    ctx: &ExecutionContext,
+    message: &AccountInfo,
+    payer: &Pubkey,
+    clock: &Clock,
+) -> Result<()> {
+    let info = match find_posted_vaa_payer(ctx.program_id, message.key, ctx.accounts) {
+        Some(info) => info,
+        None => return Ok(()),
+    };
+
+    let data = PostedVAAPayerData {
+        payer: *payer,
+        post_time: clock.unix_timestamp as u32,
+    };
+    let seeds = PostedVAAPayer::<'_, { AccountState::Uninitialized }>::bumped_seeds(
+        &PostedVAAPayerDerivationData {
+            posted_vaa: *message.key,
+        },
+        ctx.program_id,
+    );
+    let seeds: Vec<&[u8]> = seeds.iter().map(|seed| seed.as_slice()).collect();
+    create_account(
+        ctx,
+        info,
+        payer,
+        Exempt,
+        data.try_to_vec()?.len(),
+        ctx.program_id,
+        IsSigned::SignedWithSeeds(&[&seeds]),
+    )?;
+    BorshSerialize::serialize(&data, &mut *info.data.borrow_mut())?;
+    Ok(())
+}
+
+/// A guardian set must not have expired, either by governance or by the hard-coded fix below.
+///
+/// The fix stays hard-coded rather than relying on governance expiring the guardian set: a program
+/// upgrade cannot write governed state in the same step, so dropping it would let guardian set 0
+/// sign VAAs again until a governance VAA is posted. It can only go in an upgrade made after
+/// governance has expired guardian set 0 on mainnet.
 #[inline(always)]
-fn check_active<'r>(
+pub(crate) fn check_active<'r>(
     guardian_set: &GuardianSet<'r, { AccountState::Initialized }>,
     clock: &Sysvar<'r, Clock>,
 ) -> Result<()> {
@@ -172,7 +245,12 @@ fn check_active<'r>(
     Ok(())
 }
 
-// Static list of invalid signature accounts that are not allowed to post VAAs.
+/// Signature sets blocked on mainnet before governance could block them. These remain rejected
+/// alongside any signature set blocked by governance.
+///
+/// Like the guardian set 0 fix, the list stays hard-coded because an upgrade removing it would
+/// accept these signature sets until governance blocked each of them. It can only go in an upgrade
+/// made after governance has blocked all of them on mainnet.
 static INVALID_SIGNATURES: &[&str; 16] = &[
     "18eK1799CaNMGCUnnCt1Kq2uwKkax6T2WmtrDsZuVFQ",
     "2g6NCUUPaD6AxdHPQMVLpjpAvBfKMek6dDiGUe2A6T33",
@@ -192,7 +270,8 @@ static INVALID_SIGNATURES: &[&str; 16] = &[
     "FixSiDfTxvoy5Zgjp5KdFU8U23ChwCxPWY3WTkmMW2fU",
 ];
 
-/// The signatures in this instruction must be from the right guardian set.
+/// The signatures in this instruction must be from the right guardian set, and must not have been
+/// blocked, either by governance or by the hard-coded list above.
 #[inline(always)]
 fn check_valid_sigs<'r>(
     guardian_set: &GuardianSet<'r, { AccountState::Initialized }>,
@@ -207,6 +286,11 @@ fn check_valid_sigs<'r>(
         return Err(VAAInvalid.into());
     }
 
+    // Reject signature accounts blocked by governance.
+    if signatures.blocked {
+        return Err(VAAInvalid.into());
+    }
+
     Ok(())
 }
 
@@ -215,6 +299,17 @@ fn check_integrity<'r>(
     vaa: &PostVAAData,
     signatures: &SignatureSet<'r, { AccountState::Initialized }>,
 ) -> Result<()> {
+    // The body hash is expected to be the same as the hash currently stored in the signature
+    // account, binding that set of signatures to this VAA.
+    if signatures.hash != vaa_body_hash(vaa)? {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    Ok(())
+}
+
+/// Hash the body of a VAA, this is the hash signed by the guardians and the seed of the PostedVAA
+/// account the VAA is stored in.
+pub(crate) fn vaa_body_hash(vaa: &PostVAAData) -> Result<[u8; 32]> {
     // Serialize the VAA body into an array of bytes.
     let body = {
         let mut v = Cursor::new(Vec::new());
@@ -228,17 +323,8 @@ fn check_integrity<'r>(
         v.into_inner()
     };
 
-    // Hash this body, which is expected to be the same as the hash currently stored in the
-    // signature account, binding that set of signatures to this VAA.
-    let body_hash: [u8; 32] = {
-        let mut h = sha3::Keccak256::default();
-        h.write(body.as_slice())
-            .map_err(|_| ProgramError::InvalidArgument)?;
-        h.finalize().into()
-    };
-
-    if signatures.hash != body_hash {
-        return Err(ProgramError::InvalidAccountData.into());
-    }
-    Ok(())
+    let mut h = sha3::Keccak256::default();
+    h.write(body.as_slice())
+        .map_err(|_| ProgramError::InvalidArgument)?;
+    Ok(h.finalize().into())
 }
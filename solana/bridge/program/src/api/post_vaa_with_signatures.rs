@@ -0,0 +1,169 @@
+//! Post a VAA in a single transaction, verifying the guardian signatures from secp256k1
+//! instructions in the same transaction rather than from a SignatureSet.
+//!
+//! Every secp256k1 instruction placed before this one in the transaction is considered, so the
+//! signatures can be split across as many instructions as needed to fit the transaction. Only
+//! signatures over the body hash of the VAA by guardians of its guardian set are counted, each
+//! guardian at most once.
+
+use solitaire::*;
+
+use crate::{
+    accounts::{
+        Bridge,
+        GuardianSet,
+        PostedVAA,
+        PostedVAADerivationData,
+    },
+    api::{
+        post_vaa::{
+            check_active,
+            check_not_closed,
+            record_payer,
+            required_consensus_count,
+            vaa_body_hash,
+            PostVAAData,
+        },
+        verify_signature::parse_secp_instruction,
+    },
+    error::Error::{
+        InstructionAtWrongIndex,
+        PostVAAConsensusFailed,
+    },
+    events::BridgeEvent,
+};
+use solana_program::{
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+};
+
+#[derive(FromAccounts)]
+pub struct PostVAAWithSignatures<'b> {
+    /// Information about the current guardian set.
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Bridge Info
+    pub bridge_info: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Message the VAA is associated with.
+    pub message: Mut<PostedVAA<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Account used to pay for auxillary instructions.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Clock used for timestamping.
+    pub clock: Sysvar<'b, Clock>,
+
+    /// Instruction reflection account (special sysvar)
+    pub instruction_acc: Info<'b>,
+}
+
+pub fn post_vaa_with_signatures(
+    ctx: &ExecutionContext,
+    accs: &mut PostVAAWithSignatures,
+    vaa: PostVAAData,
+) -> Result<()> {
+    let body_hash = vaa_body_hash(&vaa)?;
+    let msg_derivation = PostedVAADerivationData {
+        payload_hash: body_hash.to_vec(),
+    };
+
+    accs.message
+        .verify_derivation(ctx.program_id, &msg_derivation)?;
+    accs.guardian_set
+        .verify_derivation(ctx.program_id, &(&vaa).into())?;
+
+    check_not_closed(ctx, accs.message.info())?;
+    if accs.message.is_initialized() {
+        return Ok(());
+    }
+
+    // Verify any required invariants before we process the instruction.
+    check_active(&accs.guardian_set, &accs.clock)?;
+
+    let current_instruction =
+        solana_program::sysvar::instructions::load_current_index_checked(&accs.instruction_acc)?;
+    if current_instruction == 0 {
+        return Err(InstructionAtWrongIndex.into());
+    }
+
+    // Collect the guardians that signed the body hash in any of the preceding secp instructions.
+    let mut signatures = vec![false; accs.guardian_set.keys.len()];
+    for secp_ix_index in 0..current_instruction {
+        let ix = solana_program::sysvar::instructions::load_instruction_at_checked(
+            secp_ix_index as usize,
+            &accs.instruction_acc,
+        )
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        // Other instructions in the transaction are of no concern here.
+        if ix.program_id != solana_program::secp256k1_program::id() {
+            continue;
+        }
+
+        // Secp instructions this instruction cannot parse, such as ones referring to data in other
+        // instructions, verify something else and are of no concern either.
+        let secp_ixs = match parse_secp_instruction(&ix, secp_ix_index as u8) {
+            Ok(secp_ixs) => secp_ixs,
+            Err(_) => continue,
+        };
+        if secp_ixs.is_empty() {
+            continue;
+        }
+
+        // Data must be the body hash of this VAA, secp instructions verifying anything else do not
+        // count towards consensus.
+        let (msg_offset, msg_size) = (secp_ixs[0].msg_offset, secp_ixs[0].msg_size);
+        if msg_size != 32
+            || ix.data[msg_offset as usize..(msg_offset + msg_size) as usize] != body_hash
+        {
+            continue;
+        }
+
+        for secp_ix in secp_ixs {
+            if let Some(index) = accs
+                .guardian_set
+                .keys
+                .iter()
+                .position(|key| *key == secp_ix.address)
+            {
+                signatures[index] = true;
+            }
+        }
+    }
+
+    // Count the number of distinct guardians that signed.
+    let signature_count: usize = signatures.iter().filter(|v| **v).count();
+
+    if signature_count < required_consensus_count(accs.guardian_set.keys.len()) {
+        return Err(PostVAAConsensusFailed.into());
+    }
+
+    // Persist VAA data, there is no signature set backing this VAA.
+    accs.message.nonce = vaa.nonce;
+    accs.message.emitter_chain = vaa.emitter_chain;
+    accs.message.emitter_address = vaa.emitter_address;
+    accs.message.sequence = vaa.sequence;
+    accs.message.payload = vaa.payload;
+    accs.message.consistency_level = vaa.consistency_level;
+    accs.message.vaa_version = vaa.version;
+    accs.message.vaa_time = vaa.timestamp;
+    accs.message.vaa_signature_account = Pubkey::default();
+    accs.message
+        .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
+    record_payer(ctx, accs.message.info(), accs.payer.key, &accs.clock)?;
+
+    BridgeEvent::VAAPosted {
+        posted_vaa: *accs.message.info().key,
+        guardian_set_index: accs.guardian_set.index,
+        emitter_chain: accs.message.emitter_chain,
+        emitter_address: accs.message.emitter_address,
+        sequence: accs.message.sequence,
+    }
+    .emit()
+}
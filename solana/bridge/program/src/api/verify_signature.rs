@@ -11,11 +11,15 @@ use crate::{
     GuardianSet,
     GuardianSetDerivationData,
     IsSigned::*,
+    RentPayer,
     SignatureSet,
     MAX_LEN_GUARDIAN_KEYS,
 };
 use byteorder::ByteOrder;
-use solana_program::program_error::ProgramError;
+use solana_program::{
+    instruction::Instruction,
+    program_error::ProgramError,
+};
 use solitaire::{
     processors::seeded::Seeded,
     CreationLamports::Exempt,
@@ -58,10 +62,10 @@ struct SigInfo {
     sig_index: u8,
 }
 
-struct SecpInstructionPart<'a> {
-    address: &'a [u8],
-    msg_offset: u16,
-    msg_size: u16,
+pub(crate) struct SecpInstructionPart<'a> {
+    pub(crate) address: &'a [u8],
+    pub(crate) msg_offset: u16,
+    pub(crate) msg_size: u16,
 }
 
 pub fn verify_signatures(
@@ -102,54 +106,8 @@ pub fn verify_signatures(
     )
     .map_err(|_| ProgramError::InvalidAccountData)?;
 
-    // Check that the instruction is actually for the secp program
-    if secp_ix.program_id != solana_program::secp256k1_program::id() {
-        return Err(InvalidSecpInstruction.into());
-    }
-
-    let secp_data_len = secp_ix.data.len();
-    if secp_data_len < 2 {
-        return Err(InvalidSecpInstruction.into());
-    }
-
-    let sig_len = secp_ix.data[0];
-    let mut index = 1;
-
-    let mut secp_ixs: Vec<SecpInstructionPart> = Vec::with_capacity(sig_len as usize);
-    for i in 0..sig_len {
-        let _sig_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]) as usize;
-        index += 2;
-        let sig_ix = secp_ix.data[index];
-        index += 1;
-        let address_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]) as usize;
-        index += 2;
-        let address_ix = secp_ix.data[index];
-        index += 1;
-        let msg_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]);
-        index += 2;
-        let msg_size = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]);
-        index += 2;
-        let msg_ix = secp_ix.data[index];
-        index += 1;
-
-        if address_ix != secp_ix_index || msg_ix != secp_ix_index || sig_ix != secp_ix_index {
-            return Err(InvalidSecpInstruction.into());
-        }
-
-        let address: &[u8] = &secp_ix.data[address_offset..address_offset + 20];
-
-        // Make sure that all messages are equal
-        if i > 0 {
-            if msg_offset != secp_ixs[0].msg_offset || msg_size != secp_ixs[0].msg_size {
-                return Err(InvalidSecpInstruction.into());
-            }
-        }
-        secp_ixs.push(SecpInstructionPart {
-            address,
-            msg_offset,
-            msg_size,
-        });
-    }
+    let secp_ixs = parse_secp_instruction(&secp_ix, secp_ix_index)?;
+    let sig_len = secp_ixs.len() as u8;
 
     if sig_infos.len() != secp_ixs.len() {
         return Err(ProgramError::InvalidArgument.into());
@@ -172,6 +130,9 @@ pub fn verify_signatures(
         accs.signature_set.signatures = vec![false; accs.guardian_set.keys.len()];
         accs.signature_set.guardian_set_index = accs.guardian_set.index;
         accs.signature_set.hash = msg_hash;
+        accs.signature_set.payer = Some(RentPayer {
+            payer: *accs.payer.key,
+        });
 
         let size = accs.signature_set.size();
         create_account(
@@ -216,3 +177,62 @@ pub fn verify_signatures(
 
     Ok(())
 }
+
+/// Parse the signatures checked by a secp256k1 program instruction found at `secp_ix_index` in the
+/// transaction. Every signature must refer to data within the instruction itself, and all of them
+/// must be over the same message.
+pub(crate) fn parse_secp_instruction(
+    secp_ix: &Instruction,
+    secp_ix_index: u8,
+) -> Result<Vec<SecpInstructionPart>> {
+    // Check that the instruction is actually for the secp program
+    if secp_ix.program_id != solana_program::secp256k1_program::id() {
+        return Err(InvalidSecpInstruction.into());
+    }
+
+    let secp_data_len = secp_ix.data.len();
+    if secp_data_len < 2 {
+        return Err(InvalidSecpInstruction.into());
+    }
+
+    let sig_len = secp_ix.data[0];
+    let mut index = 1;
+
+    let mut secp_ixs: Vec<SecpInstructionPart> = Vec::with_capacity(sig_len as usize);
+    for i in 0..sig_len {
+        let _sig_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]) as usize;
+        index += 2;
+        let sig_ix = secp_ix.data[index];
+        index += 1;
+        let address_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]) as usize;
+        index += 2;
+        let address_ix = secp_ix.data[index];
+        index += 1;
+        let msg_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]);
+        index += 2;
+        let msg_size = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]);
+        index += 2;
+        let msg_ix = secp_ix.data[index];
+        index += 1;
+
+        if address_ix != secp_ix_index || msg_ix != secp_ix_index || sig_ix != secp_ix_index {
+            return Err(InvalidSecpInstruction.into());
+        }
+
+        let address: &[u8] = &secp_ix.data[address_offset..address_offset + 20];
+
+        // Make sure that all messages are equal
+        if i > 0 {
+            if msg_offset != secp_ixs[0].msg_offset || msg_size != secp_ixs[0].msg_size {
+                return Err(InvalidSecpInstruction.into());
+            }
+        }
+        secp_ixs.push(SecpInstructionPart {
+            address,
+            msg_offset,
+            msg_size,
+        });
+    }
+
+    Ok(secp_ixs)
+}
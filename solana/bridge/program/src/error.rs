@@ -26,6 +26,12 @@ pub enum Error {
     VAAInvalid,
     InvalidPayloadLength,
     EmitterChanged,
+    InvalidCloseAuthority,
+    InvalidGuardianSetExpiration,
+    MissingFeeSchedule,
+    VAAClosed,
+    VAATooRecent,
+    SignatureSetMismatch,
 }
 
 /// Errors thrown by the program will bubble up to the solitaire wrapper, which needs a way to
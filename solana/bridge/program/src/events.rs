@@ -0,0 +1,127 @@
+//! Events logged by the bridge whenever it changes state, so that indexers can follow the bridge
+//! from transaction logs alone.
+//!
+//! Each event is logged with `sol_log_data` as two fields, `EVENT_TAG` followed by the Borsh
+//! encoding of `(EVENT_VERSION, BridgeEvent)`, and shows up in the transaction logs as a base64
+//! encoded `Program data:` line. New events must only ever be appended to `BridgeEvent`, as the
+//! variant index is part of the encoding.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    log::sol_log_data,
+    pubkey::Pubkey,
+};
+use solitaire::Result;
+
+use crate::types::GuardianPublicKey;
+
+/// Tag identifying bridge events among other program data logged in a transaction.
+pub const EVENT_TAG: &[u8] = b"WormholeBridgeEvent";
+
+/// Version of the event encoding, bumped whenever existing events change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub enum BridgeEvent {
+    /// The bridge was initialized.
+    Initialized {
+        guardian_set_index: u32,
+        guardian_set: Vec<GuardianPublicKey>,
+        guardian_set_expiration_time: u32,
+        fee: u64,
+    },
+
+    /// A message was posted, reliably or not, and is waiting to be observed by the guardians.
+    MessagePosted {
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        nonce: u32,
+        consistency_level: u8,
+        payload_len: u32,
+        fee: u64,
+    },
+
+    /// A VAA was verified and stored.
+    VAAPosted {
+        posted_vaa: Pubkey,
+        guardian_set_index: u32,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        sequence: u64,
+    },
+
+    /// The active guardian set was replaced by a new one.
+    GuardianSetUpgraded {
+        old_guardian_set_index: u32,
+        old_guardian_set_expiration_time: u32,
+        new_guardian_set_index: u32,
+        new_guardian_set: Vec<GuardianPublicKey>,
+    },
+
+    /// A guardian set was expired by governance.
+    GuardianSetExpired {
+        guardian_set_index: u32,
+        expiration_time: u32,
+    },
+
+    /// A signature set was blocked from posting VAAs by governance.
+    SignatureSetBlocked { signature_set: Pubkey },
+
+    /// The bridge-wide message fee schedule changed, `fee_per_byte` is `None` when only the flat
+    /// fee was set.
+    MessageFeeChanged { fee: u64, fee_per_byte: Option<u64> },
+
+    /// The message fee override of an emitter changed.
+    EmitterMessageFeeChanged {
+        emitter: Pubkey,
+        fee: u64,
+        fee_per_byte: u64,
+    },
+
+    /// Collected fees were transferred out of the fee collector.
+    FeesTransferred { recipient: Pubkey, amount: u64 },
+
+    /// The bridge program was upgraded.
+    ContractUpgraded { new_contract: Pubkey },
+
+    /// A signature set was closed by its payer, who received its rent.
+    SignatureSetClosed {
+        signature_set: Pubkey,
+        recipient: Pubkey,
+    },
+
+    /// A posted VAA was closed by its payer, who received its rent. The account is left behind
+    /// empty, so the VAA cannot be posted again.
+    PostedVAAClosed {
+        posted_vaa: Pubkey,
+        recipient: Pubkey,
+    },
+}
+
+impl BridgeEvent {
+    /// Log the event to the transaction logs.
+    pub fn emit(&self) -> Result<()> {
+        let data = (EVENT_VERSION, self).try_to_vec()?;
+        sol_log_data(&[EVENT_TAG, &data]);
+        Ok(())
+    }
+
+    /// Decode the fields of a `Program data:` log line, after base64 decoding them. Returns `None`
+    /// for data that is not a bridge event of the current version.
+    pub fn decode(fields: &[&[u8]]) -> Option<BridgeEvent> {
+        match fields {
+            [tag, data] if *tag == EVENT_TAG => {
+                let (version, event) = <(u8, BridgeEvent)>::try_from_slice(data).ok()?;
+                match version {
+                    EVENT_VERSION => Some(event),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
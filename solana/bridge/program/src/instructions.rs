@@ -24,6 +24,8 @@ use std::io::{
 
 use crate::{
     accounts::{
+        fee_schedule_key,
+        posted_vaa_payer_key,
         Bridge,
         Claim,
         ClaimDerivationData,
@@ -36,10 +38,16 @@ use crate::{
         SequenceDerivationData,
     },
     types::ConsistencyLevel,
+    BlockSignatureSetData,
+    ClosePostedVAAData,
+    CloseSignatureSetData,
+    ExpireGuardianSetData,
     InitializeData,
     PostMessageData,
     PostVAAData,
+    SetEmitterMessageFeeData,
     SetFeesData,
+    SetMessageFeeScheduleData,
     TransferFeesData,
     UpgradeContractData,
     UpgradeGuardianSetData,
@@ -114,6 +122,7 @@ pub fn post_message(
             AccountMeta::new_readonly(sysvar::clock::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(fee_schedule_key(&program_id), false),
         ],
         data: (
             crate::instruction::Instruction::PostMessage,
@@ -157,6 +166,7 @@ pub fn post_message_unreliable(
             AccountMeta::new_readonly(sysvar::clock::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(fee_schedule_key(&program_id), false),
         ],
         data: (
             crate::instruction::Instruction::PostMessageUnreliable,
@@ -233,6 +243,7 @@ pub fn post_vaa(
             AccountMeta::new_readonly(sysvar::clock::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(posted_vaa_payer_key(&program_id, &message), false),
         ],
 
         data: (crate::instruction::Instruction::PostVAA, vaa)
@@ -241,6 +252,102 @@ pub fn post_vaa(
     }
 }
 
+/// Post a VAA verified by the secp256k1 instructions preceding this one in the same transaction,
+/// without a signature set.
+pub fn post_vaa_with_signatures(
+    program_id: Pubkey,
+    payer: Pubkey,
+    vaa: PostVAAData,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: vaa.guardian_set_index,
+        },
+        &program_id,
+    );
+
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: hash_vaa(&vaa).to_vec(),
+    };
+
+    let message =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new(message, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(posted_vaa_payer_key(&program_id, &message), false),
+        ],
+
+        data: (crate::instruction::Instruction::PostVAAWithSignatures, vaa)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Close a signature set, returning its rent to `authority`, its payer. Signature sets created
+/// before payers were recorded must instead be signed by the signature set keypair.
+pub fn close_signature_set(
+    program_id: Pubkey,
+    authority: Pubkey,
+    guardian_set_index: u32,
+    signature_set: Pubkey,
+) -> Instruction {
+    let guardian_set = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new(signature_set, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CloseSignatureSet,
+            CloseSignatureSetData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Close a posted VAA, returning its rent to `authority`, its payer.
+pub fn close_posted_vaa(program_id: Pubkey, authority: Pubkey, posted_vaa: Pubkey) -> Instruction {
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(posted_vaa, false),
+            AccountMeta::new(posted_vaa_payer_key(&program_id, &posted_vaa), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ClosePostedVAA,
+            ClosePostedVAAData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 pub fn upgrade_contract(
     program_id: Pubkey,
     payer: Pubkey,
@@ -423,6 +530,173 @@ pub fn transfer_fees(
     }
 }
 
+pub fn expire_guardian_set(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    guardian_set_index: u32,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let guardian_set = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(guardian_set, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ExpireGuardianSet,
+            ExpireGuardianSetData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn block_signature_set(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    signature_set: Pubkey,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(signature_set, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::BlockSignatureSet,
+            BlockSignatureSetData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_message_fee_schedule(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(fee_schedule_key(&program_id), false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetMessageFeeSchedule,
+            SetMessageFeeScheduleData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_emitter_message_fee(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    fee_emitter: Pubkey,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let fee_emitter_sequence = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &fee_emitter,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(fee_schedule_key(&program_id), false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(fee_emitter_sequence, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetEmitterMessageFee,
+            SetEmitterMessageFeeData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 // Convert a full VAA structure into the serialization of its unique components, this structure is
 // what is hashed and verified by Guardians.
 pub fn serialize_vaa(vaa: &PostVAAData) -> Vec<u8> {
@@ -27,17 +27,25 @@ pub use accounts::{
     Claim,
     ClaimData,
     ClaimDerivationData,
+    EmitterMessageFee,
     FeeCollector,
+    FeeSchedule,
+    FeeScheduleData,
     GuardianSet,
     GuardianSetData,
     GuardianSetDerivationData,
     MessageData,
+    MessageFee,
     PostedMessage,
     PostedMessageData,
     PostedMessageUnreliable,
     PostedMessageUnreliableData,
     PostedVAA,
     PostedVAAData,
+    PostedVAAPayer,
+    PostedVAAPayerData,
+    PostedVAAPayerDerivationData,
+    RentPayer,
     Sequence,
     SequenceDerivationData,
     SequenceTracker,
@@ -48,15 +56,30 @@ pub use accounts::{
 pub mod api;
 
 pub use api::{
+    block_signature_set,
+    close_posted_vaa,
+    close_signature_set,
+    expire_guardian_set,
     initialize,
     post_message,
     post_message_unreliable,
     post_vaa,
+    post_vaa_with_signatures,
+    set_emitter_message_fee,
     set_fees,
+    set_message_fee_schedule,
     transfer_fees,
     upgrade_contract,
     upgrade_guardian_set,
     verify_signatures,
+    BlockSignatureSet,
+    BlockSignatureSetData,
+    ClosePostedVAA,
+    ClosePostedVAAData,
+    CloseSignatureSet,
+    CloseSignatureSetData,
+    ExpireGuardianSet,
+    ExpireGuardianSetData,
     Initialize,
     InitializeData,
     PostMessage,
@@ -64,8 +87,13 @@ pub use api::{
     PostMessageUnreliable,
     PostVAA,
     PostVAAData,
+    PostVAAWithSignatures,
+    SetEmitterMessageFee,
+    SetEmitterMessageFeeData,
     SetFees,
     SetFeesData,
+    SetMessageFeeSchedule,
+    SetMessageFeeScheduleData,
     Signature,
     TransferFees,
     TransferFeesData,
@@ -79,6 +107,7 @@ pub use api::{
 };
 
 pub mod error;
+pub mod events;
 pub mod types;
 pub mod vaa;
 
@@ -100,4 +129,11 @@ solitaire! {
     UpgradeGuardianSet => upgrade_guardian_set,
     VerifySignatures   => verify_signatures,
     PostMessageUnreliable        => post_message_unreliable,
+    CloseSignatureSet            => close_signature_set,
+    ClosePostedVAA               => close_posted_vaa,
+    PostVAAWithSignatures        => post_vaa_with_signatures,
+    ExpireGuardianSet            => expire_guardian_set,
+    BlockSignatureSet            => block_signature_set,
+    SetMessageFeeSchedule        => set_message_fee_schedule,
+    SetEmitterMessageFee         => set_emitter_message_fee,
 }
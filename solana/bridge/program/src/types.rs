@@ -233,3 +233,199 @@ impl SerializeGovernancePayload for GovernancePayloadTransferFees {
 
 impl DeserializeGovernancePayload for GovernancePayloadTransferFees {
 }
+
+pub struct GovernancePayloadExpireGuardianSet {
+    // Index of the guardian set to expire
+    pub guardian_set_index: u32,
+}
+
+impl SerializePayload for GovernancePayloadExpireGuardianSet {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        use byteorder::WriteBytesExt;
+        v.write_u32::<BigEndian>(self.guardian_set_index)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadExpireGuardianSet
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let guardian_set_index = c.read_u32::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadExpireGuardianSet { guardian_set_index })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadExpireGuardianSet {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 5;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadExpireGuardianSet {
+}
+
+pub struct GovernancePayloadBlockSignatureSet {
+    // Signature set that may no longer post VAAs
+    pub signature_set: Pubkey,
+}
+
+impl SerializePayload for GovernancePayloadBlockSignatureSet {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.signature_set.to_bytes())?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadBlockSignatureSet
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut addr = [0u8; 32];
+        c.read_exact(&mut addr)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadBlockSignatureSet {
+            signature_set: Pubkey::new(&addr[..]),
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadBlockSignatureSet {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadBlockSignatureSet {
+}
+
+pub struct GovernancePayloadSetMessageFeeSchedule {
+    // New base fee in lamports
+    pub fee: U256,
+
+    // New fee per byte of payload in lamports
+    pub fee_per_byte: U256,
+}
+
+impl SerializePayload for GovernancePayloadSetMessageFeeSchedule {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        let mut fee_data = [0u8; 32];
+        self.fee.to_big_endian(&mut fee_data);
+        v.write_all(&fee_data[..])?;
+
+        self.fee_per_byte.to_big_endian(&mut fee_data);
+        v.write_all(&fee_data[..])?;
+
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetMessageFeeSchedule
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut fee_data: [u8; 32] = [0; 32];
+        c.read_exact(&mut fee_data)?;
+        let fee = U256::from_big_endian(&fee_data);
+
+        c.read_exact(&mut fee_data)?;
+        let fee_per_byte = U256::from_big_endian(&fee_data);
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetMessageFeeSchedule { fee, fee_per_byte })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetMessageFeeSchedule {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 7;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetMessageFeeSchedule {
+}
+
+pub struct GovernancePayloadSetEmitterMessageFee {
+    // Emitter the fee schedule applies to
+    pub emitter: Pubkey,
+
+    // Base fee in lamports
+    pub fee: U256,
+
+    // Fee per byte of payload in lamports
+    pub fee_per_byte: U256,
+}
+
+impl SerializePayload for GovernancePayloadSetEmitterMessageFee {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.emitter.to_bytes())?;
+
+        let mut fee_data = [0u8; 32];
+        self.fee.to_big_endian(&mut fee_data);
+        v.write_all(&fee_data[..])?;
+
+        self.fee_per_byte.to_big_endian(&mut fee_data);
+        v.write_all(&fee_data[..])?;
+
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetEmitterMessageFee
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut addr = [0u8; 32];
+        c.read_exact(&mut addr)?;
+
+        let mut fee_data: [u8; 32] = [0; 32];
+        c.read_exact(&mut fee_data)?;
+        let fee = U256::from_big_endian(&fee_data);
+
+        c.read_exact(&mut fee_data)?;
+        let fee_per_byte = U256::from_big_endian(&fee_data);
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetEmitterMessageFee {
+            emitter: Pubkey::new(&addr[..]),
+            fee,
+            fee_per_byte,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetEmitterMessageFee {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 8;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetEmitterMessageFee {
+}
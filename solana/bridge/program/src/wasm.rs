@@ -30,12 +30,20 @@ use crate::{
         PostedVAAData,
         PostedVAADerivationData,
     },
+    api::post_vaa::required_consensus_count,
     instructions::{
+        block_signature_set,
+        close_posted_vaa,
+        close_signature_set,
+        expire_guardian_set,
         hash_vaa,
         post_message,
         post_message_unreliable,
         post_vaa,
+        post_vaa_with_signatures,
+        set_emitter_message_fee,
         set_fees,
+        set_message_fee_schedule,
         transfer_fees,
         upgrade_contract,
         upgrade_guardian_set,
@@ -43,7 +51,10 @@ use crate::{
     },
     types::{
         ConsistencyLevel,
+        GovernancePayloadBlockSignatureSet,
+        GovernancePayloadExpireGuardianSet,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetEmitterMessageFee,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
     },
@@ -139,6 +150,51 @@ pub fn post_vaa_ix(
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn close_signature_set_ix(
+    program_id: String,
+    authority: String,
+    guardian_set_index: u32,
+    signature_set: String,
+) -> JsValue {
+    let ix = close_signature_set(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(authority.as_str()).unwrap(),
+        guardian_set_index,
+        Pubkey::from_str(signature_set.as_str()).unwrap(),
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn close_posted_vaa_ix(program_id: String, authority: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let vaa = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+    let posted_vaa = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = close_posted_vaa(
+        program_id,
+        Pubkey::from_str(authority.as_str()).unwrap(),
+        posted_vaa,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn update_guardian_set_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
@@ -183,6 +239,49 @@ pub fn set_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn set_message_fee_schedule_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_message_fee_schedule(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_emitter_message_fee_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadSetEmitterMessageFee::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_emitter_message_fee(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+        payload.emitter,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn transfer_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
@@ -205,6 +304,52 @@ pub fn transfer_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsVa
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn expire_guardian_set_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadExpireGuardianSet::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = expire_guardian_set(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+        payload.guardian_set_index,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn block_signature_set_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadBlockSignatureSet::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = block_signature_set(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+        payload.signature_set,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn upgrade_contract_ix(
     program_id: String,
@@ -272,49 +417,12 @@ pub fn verify_signatures_ix(
 
     let mut verify_txs: Vec<Vec<Instruction>> = Vec::new();
     for (_tx_index, chunk) in signature_items.chunks(7).enumerate() {
-        let mut secp_payload = Vec::new();
         let mut signature_status = [-1i8; 19];
-
-        let data_offset = 1 + chunk.len() * 11;
-        let message_offset = data_offset + chunk.len() * 85;
-
-        // 1 number of signatures
-        secp_payload.write_u8(chunk.len() as u8).unwrap();
-
-        // Secp signature info description (11 bytes * n)
         for (i, s) in chunk.iter().enumerate() {
-            secp_payload
-                .write_u16::<LittleEndian>((data_offset + 85 * i) as u16)
-                .unwrap();
-            secp_payload.write_u8(0).unwrap();
-            secp_payload
-                .write_u16::<LittleEndian>((data_offset + 85 * i + 65) as u16)
-                .unwrap();
-            secp_payload.write_u8(0).unwrap();
-            secp_payload
-                .write_u16::<LittleEndian>(message_offset as u16)
-                .unwrap();
-            secp_payload
-                .write_u16::<LittleEndian>(body_hash.len() as u16)
-                .unwrap();
-            secp_payload.write_u8(0).unwrap();
             signature_status[s.index as usize] = i as i8;
         }
 
-        // Write signatures and addresses
-        for s in chunk.iter() {
-            secp_payload.write(&s.signature).unwrap();
-            secp_payload.write(&s.key).unwrap();
-        }
-
-        // Write body
-        secp_payload.write(&body_hash).unwrap();
-
-        let secp_ix = Instruction {
-            program_id: solana_program::secp256k1_program::id(),
-            data: secp_payload,
-            accounts: vec![],
-        };
+        let secp_ix = secp_instruction(chunk, &body_hash, 0);
 
         let payload = VerifySignaturesData {
             signers: signature_status,
@@ -337,6 +445,117 @@ pub fn verify_signatures_ix(
     JsValue::from_serde(&verify_txs).unwrap()
 }
 
+/// Build the instructions to post a VAA in a single transaction: secp256k1 instructions verifying
+/// the signatures of the VAA, followed by the instruction posting it. The instructions must be the
+/// first in the transaction, as each secp256k1 instruction refers to its own index.
+#[wasm_bindgen]
+pub fn post_vaa_with_signatures_ix(
+    program_id: String,
+    payer: String,
+    guardian_set: JsValue,
+    vaa_data: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+
+    let guardian_set: GuardianSetData = guardian_set.into_serde().unwrap();
+    let vaa = VAA::deserialize(vaa_data.as_slice()).unwrap();
+
+    // Map signatures to guardian set, only a quorum of them needs to be verified and every further
+    // signature would only take up space in the transaction.
+    let signature_items: Vec<SignatureItem> = vaa
+        .signatures
+        .iter()
+        .take(required_consensus_count(guardian_set.keys.len()))
+        .map(|s| SignatureItem {
+            signature: s.signature.clone(),
+            key: guardian_set.keys[s.guardian_index as usize],
+            index: s.guardian_index as u8,
+        })
+        .collect();
+
+    let vaa_body = &vaa_data[VAA::HEADER_LEN + VAA::SIGNATURE_LEN * vaa.signatures.len()..];
+    let body_hash: [u8; 32] = {
+        let mut h = sha3::Keccak256::default();
+        h.write(vaa_body).unwrap();
+        h.finalize().into()
+    };
+
+    let mut ixs: Vec<Instruction> = signature_items
+        .chunks(7)
+        .enumerate()
+        .map(|(ix_index, chunk)| secp_instruction(chunk, &body_hash, ix_index as u8))
+        .collect();
+
+    ixs.push(post_vaa_with_signatures(
+        program_id,
+        payer,
+        PostVAAData {
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            timestamp: vaa.timestamp,
+            nonce: vaa.nonce,
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            consistency_level: vaa.consistency_level,
+            payload: vaa.payload,
+        },
+    ));
+
+    JsValue::from_serde(&ixs).unwrap()
+}
+
+/// Build a secp256k1 instruction verifying `signatures` of `body_hash`, to be placed at index
+/// `ix_index` of its transaction.
+fn secp_instruction(
+    signatures: &[SignatureItem],
+    body_hash: &[u8; 32],
+    ix_index: u8,
+) -> Instruction {
+    let mut secp_payload = Vec::new();
+
+    let data_offset = 1 + signatures.len() * 11;
+    let message_offset = data_offset + signatures.len() * 85;
+
+    // 1 number of signatures
+    secp_payload.write_u8(signatures.len() as u8).unwrap();
+
+    // Secp signature info description (11 bytes * n)
+    for i in 0..signatures.len() {
+        secp_payload
+            .write_u16::<LittleEndian>((data_offset + 85 * i) as u16)
+            .unwrap();
+        secp_payload.write_u8(ix_index).unwrap();
+        secp_payload
+            .write_u16::<LittleEndian>((data_offset + 85 * i + 65) as u16)
+            .unwrap();
+        secp_payload.write_u8(ix_index).unwrap();
+        secp_payload
+            .write_u16::<LittleEndian>(message_offset as u16)
+            .unwrap();
+        secp_payload
+            .write_u16::<LittleEndian>(body_hash.len() as u16)
+            .unwrap();
+        secp_payload.write_u8(ix_index).unwrap();
+    }
+
+    // Write signatures and addresses
+    for s in signatures.iter() {
+        secp_payload.write(&s.signature).unwrap();
+        secp_payload.write(&s.key).unwrap();
+    }
+
+    // Write body
+    secp_payload.write(body_hash).unwrap();
+
+    Instruction {
+        program_id: solana_program::secp256k1_program::id(),
+        data: secp_payload,
+        accounts: vec![],
+    }
+}
+
 #[wasm_bindgen]
 pub fn guardian_set_address(bridge: String, index: u32) -> Vec<u8> {
     let program_id = Pubkey::from_str(bridge.as_str()).unwrap();
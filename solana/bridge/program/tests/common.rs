@@ -1,6 +1,10 @@
-use borsh::BorshDeserialize;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
 use byteorder::{
     BigEndian,
+    LittleEndian,
     WriteBytesExt,
 };
 
@@ -12,6 +16,7 @@ use sha3::Digest;
 use solana_program::{
     instruction::Instruction,
     pubkey::Pubkey,
+    rent::Rent,
     system_instruction,
 };
 use solana_program_test::{
@@ -20,6 +25,7 @@ use solana_program_test::{
     ProgramTest,
 };
 use solana_sdk::{
+    account::Account,
     commitment_config::CommitmentLevel,
     secp256k1_instruction::new_secp256k1_instruction,
     signature::{
@@ -39,15 +45,27 @@ use std::{
 };
 
 use bridge::{
-    accounts::FeeCollector,
+    accounts::{
+        posted_vaa_payer_key,
+        FeeCollector,
+        PostedVAA,
+        PostedVAAData,
+        PostedVAADerivationData,
+        PostedVAAPayerData,
+    },
+    events::BridgeEvent,
     instruction,
     instructions,
     types::ConsistencyLevel,
+    MessageData,
     PostVAAData,
     VerifySignaturesData,
 };
 
-use solitaire::processors::seeded::Seeded;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
 
 pub use helpers::*;
 
@@ -68,18 +86,84 @@ pub async fn execute<T: Signers>(
         .await
 }
 
+/// Execute the instructions like `execute`, returning the bridge events they logged.
+///
+/// Transaction logs are only returned when simulating a transaction fails, so the instructions are
+/// first simulated followed by a transfer the payer cannot afford, and then executed for real.
+pub async fn execute_with_events<T: Signers>(
+    client: &mut BanksClient,
+    payer: &Keypair,
+    signers: &T,
+    instructions: &[Instruction],
+    commitment_level: CommitmentLevel,
+) -> Result<Vec<BridgeEvent>, BanksClientError> {
+    let failing = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), u64::MAX);
+    let mut transaction =
+        Transaction::new_with_payer(&[instructions, &[failing]].concat(), Some(&payer.pubkey()));
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    transaction.sign(signers, recent_blockhash);
+
+    let logs = match client
+        .process_transaction_with_preflight_and_commitment(transaction, commitment_level)
+        .await
+    {
+        Err(BanksClientError::SimulationError { logs, .. }) => logs,
+        result => panic!("Simulation unexpectedly succeeded: {:?}", result),
+    };
+
+    // Events are logged as base64 encoded fields separated by spaces.
+    let events = logs
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| {
+            let fields = data
+                .split(' ')
+                .map(base64::decode)
+                .collect::<Result<Vec<_>, _>>()
+                .ok()?;
+            let fields: Vec<&[u8]> = fields.iter().map(Vec::as_slice).collect();
+            BridgeEvent::decode(&fields)
+        })
+        .collect();
+
+    execute(client, payer, signers, instructions, commitment_level).await?;
+    Ok(events)
+}
+
 mod helpers {
     use super::*;
     use solana_program_test::processor;
 
-    /// Initialize the test environment, spins up a solana-test-validator in the background so that
-    /// each test has a fresh environment to work within.
-    pub async fn setup() -> (BanksClient, Keypair, Pubkey) {
-        let program = env::var("BRIDGE_PROGRAM")
+    /// Address of the bridge program under test.
+    pub fn program_id() -> Pubkey {
+        env::var("BRIDGE_PROGRAM")
             .unwrap_or_else(|_| "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o".to_string())
             .parse::<Pubkey>()
-            .unwrap();
-        let builder = ProgramTest::new("bridge", program, processor!(instruction::solitaire));
+            .unwrap()
+    }
+
+    /// Initialize the test environment, spins up a solana-test-validator in the background so that
+    /// each test has a fresh environment to work within.
+    ///
+    /// The environment starts with rent exempt accounts owned by the bridge holding the given data,
+    /// to test against accounts left behind by earlier versions of the bridge.
+    pub async fn setup_with_accounts(
+        accounts: Vec<(Pubkey, Vec<u8>)>,
+    ) -> (BanksClient, Keypair, Pubkey) {
+        let program = program_id();
+        let mut builder = ProgramTest::new("bridge", program, processor!(instruction::solitaire));
+        for (address, data) in accounts {
+            builder.add_account(
+                address,
+                Account {
+                    lamports: Rent::default().minimum_balance(data.len()),
+                    data,
+                    owner: program,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            );
+        }
 
         let (client, payer, _) = builder.start().await;
 
@@ -142,6 +226,45 @@ mod helpers {
         )
     }
 
+    /// Accounts holding `vaa` as posted by `payer` at `post_time`, with `body` the hash of its body,
+    /// to start a test environment with.
+    pub fn posted_vaa_accounts(
+        vaa: &PostVAAData,
+        body: [u8; 32],
+        payer: Pubkey,
+        post_time: u32,
+    ) -> Vec<(Pubkey, Vec<u8>)> {
+        let program = program_id();
+        let posted_vaa = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: body.to_vec(),
+            },
+            &program,
+        );
+        let data = PostedVAAData {
+            message: MessageData {
+                vaa_version: vaa.version,
+                consistency_level: vaa.consistency_level,
+                vaa_time: vaa.timestamp,
+                nonce: vaa.nonce,
+                sequence: vaa.sequence,
+                emitter_chain: vaa.emitter_chain,
+                emitter_address: vaa.emitter_address,
+                payload: vaa.payload.clone(),
+                ..Default::default()
+            },
+        };
+        let record = PostedVAAPayerData { payer, post_time };
+
+        vec![
+            (posted_vaa, data.try_to_vec().unwrap()),
+            (
+                posted_vaa_payer_key(&program, &posted_vaa),
+                record.try_to_vec().unwrap(),
+            ),
+        ]
+    }
+
     /// Utility function for generating VAA's from message data.
     pub fn generate_vaa(
         emitter: &Keypair,
@@ -271,6 +394,47 @@ mod helpers {
         Ok(message.pubkey())
     }
 
+    /// Post a message like `post_message`, with the accounts of instructions built before fee
+    /// schedules and so without the fee schedule.
+    pub async fn post_message_without_fee_schedule(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        nonce: u32,
+        data: Vec<u8>,
+        fee: u64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let fee_collector = FeeCollector::<'_>::key(None, program);
+        let message = Keypair::new();
+
+        let mut instruction = instructions::post_message(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            message.pubkey(),
+            nonce,
+            data,
+            ConsistencyLevel::Confirmed,
+        )
+        .unwrap();
+        instruction.accounts.pop();
+
+        execute(
+            client,
+            payer,
+            &[payer, emitter, &message],
+            &[
+                system_instruction::transfer(&payer.pubkey(), &fee_collector, fee),
+                instruction,
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await?;
+
+        Ok(message.pubkey())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn post_message_unreliable(
         client: &mut BanksClient,
@@ -371,6 +535,124 @@ mod helpers {
         .await
     }
 
+    pub async fn post_vaa_with_signatures(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        body: [u8; 32],
+        secret_keys: &[SecretKey],
+        vaa: PostVAAData,
+    ) -> Result<(), BanksClientError> {
+        post_vaa_with_signatures_after(client, program, payer, Vec::new(), body, secret_keys, vaa)
+            .await
+    }
+
+    /// Post a VAA in a single transaction as `post_vaa_with_signatures` does, placing `preceding`
+    /// instructions before the ones verifying the signatures.
+    pub async fn post_vaa_with_signatures_after(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        preceding: Vec<Instruction>,
+        body: [u8; 32],
+        secret_keys: &[SecretKey],
+        vaa: PostVAAData,
+    ) -> Result<(), BanksClientError> {
+        // Verify all signatures in a single Secp256k1 instruction, in the same transaction as the
+        // post.
+        let mut ixs = preceding;
+        ixs.push(secp256k1_instruction(secret_keys, &body, ixs.len() as u8));
+        ixs.push(instructions::post_vaa_with_signatures(
+            *program,
+            payer.pubkey(),
+            vaa,
+        ));
+
+        execute(client, payer, &[payer], &ixs, CommitmentLevel::Processed).await
+    }
+
+    /// Build a Secp256k1 instruction verifying a signature of `body` by each of `secret_keys`, all
+    /// referring to data within the instruction itself when placed at `ix_index` of a transaction.
+    pub fn secp256k1_instruction(
+        secret_keys: &[SecretKey],
+        body: &[u8; 32],
+        ix_index: u8,
+    ) -> Instruction {
+        // Per signature the instruction holds 11 bytes of offsets, followed by the signer address,
+        // signature and recovery ID. The message they all sign comes last.
+        let data_offset = 1 + secret_keys.len() * 11;
+        let message_offset = data_offset + secret_keys.len() * 85;
+
+        let mut offsets = vec![secret_keys.len() as u8];
+        let mut data = Vec::new();
+        for (i, key) in secret_keys.iter().enumerate() {
+            let address_offset = (data_offset + i * 85) as u16;
+            offsets
+                .write_u16::<LittleEndian>(address_offset + 20)
+                .unwrap();
+            offsets.write_u8(ix_index).unwrap();
+            offsets.write_u16::<LittleEndian>(address_offset).unwrap();
+            offsets.write_u8(ix_index).unwrap();
+            offsets
+                .write_u16::<LittleEndian>(message_offset as u16)
+                .unwrap();
+            offsets
+                .write_u16::<LittleEndian>(body.len() as u16)
+                .unwrap();
+            offsets.write_u8(ix_index).unwrap();
+
+            // Address, signature and recovery ID as laid out by a single signature instruction.
+            let single = new_secp256k1_instruction(key, body);
+            data.extend_from_slice(&single.data[12..97]);
+        }
+
+        let mut ix = new_secp256k1_instruction(&secret_keys[0], body);
+        ix.data = [offsets, data, body.to_vec()].concat();
+        ix
+    }
+
+    pub async fn close_signature_set(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        authority: &Keypair,
+        guardian_set_index: u32,
+        signature_set: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            authority,
+            &[authority],
+            &[instructions::close_signature_set(
+                *program,
+                authority.pubkey(),
+                guardian_set_index,
+                signature_set,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn close_posted_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        authority: &Keypair,
+        posted_vaa: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            authority,
+            &[authority],
+            &[instructions::close_posted_vaa(
+                *program,
+                authority.pubkey(),
+                posted_vaa,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn upgrade_guardian_set(
         client: &mut BanksClient,
@@ -453,6 +735,108 @@ mod helpers {
         .await
     }
 
+    pub async fn expire_guardian_set(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        guardian_set_index: u32,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::expire_guardian_set(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                guardian_set_index,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn block_signature_set(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        signature_set: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::block_signature_set(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                signature_set,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_message_fee_schedule(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_message_fee_schedule(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_emitter_message_fee(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        fee_emitter: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_emitter_message_fee(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                fee_emitter,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn transfer_fees(
         client: &mut BanksClient,
         program: &Pubkey,
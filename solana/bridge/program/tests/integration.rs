@@ -1,6 +1,7 @@
 use libsecp256k1::SecretKey;
 use rand::Rng;
 use solana_program::{
+    instruction::AccountMeta,
     pubkey::Pubkey,
     system_instruction,
 };
@@ -22,25 +23,41 @@ use solitaire::{
 
 use bridge::{
     accounts::{
+        fee_schedule_key,
+        posted_vaa_payer_key,
         Bridge,
         BridgeData,
+        Claim,
+        ClaimDerivationData,
         FeeCollector,
+        FeeScheduleData,
         GuardianSet,
         GuardianSetData,
         GuardianSetDerivationData,
+        MessageFee,
         PostedVAA,
         PostedVAAData,
         PostedVAADerivationData,
+        PostedVAAPayerData,
+        Sequence,
+        SequenceDerivationData,
+        SequenceTracker,
         SignatureSetData,
     },
+    events::BridgeEvent,
     instructions,
     types::{
         ConsistencyLevel,
+        GovernancePayloadBlockSignatureSet,
+        GovernancePayloadExpireGuardianSet,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetEmitterMessageFee,
         GovernancePayloadSetMessageFee,
+        GovernancePayloadSetMessageFeeSchedule,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
     },
+    BridgeConfig,
     SerializeGovernancePayload,
 };
 use primitive_types::U256;
@@ -79,6 +96,13 @@ impl Sequencer {
 }
 
 async fn initialize() -> (Context, BanksClient, Keypair, Pubkey) {
+    initialize_with_accounts(Vec::new()).await
+}
+
+/// Initialize a bridge that already holds the given accounts, see `common::setup_with_accounts`.
+async fn initialize_with_accounts(
+    accounts: Vec<(Pubkey, Vec<u8>)>,
+) -> (Context, BanksClient, Keypair, Pubkey) {
     let (public_keys, secret_keys) = common::generate_keys(6);
     let context = Context {
         public: public_keys,
@@ -87,7 +111,7 @@ async fn initialize() -> (Context, BanksClient, Keypair, Pubkey) {
             sequences: std::collections::HashMap::new(),
         },
     };
-    let (mut client, payer, program) = common::setup().await;
+    let (mut client, payer, program) = common::setup_with_accounts(accounts).await;
 
     // Use a timestamp from a few seconds earlier for testing to simulate thread::sleep();
     let now = std::time::SystemTime::now()
@@ -671,6 +695,76 @@ async fn invalid_emitter() {
     .is_err());
 }
 
+// Programs and clients posting messages append the fee schedule to the accounts they already pass,
+// while those that were not upgraded keep posting without it. This relies on the bridge ignoring
+// accounts it does not expect, as bridges deployed before fee schedules do.
+#[tokio::test]
+async fn post_message_fee_schedule_account() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let fee_collector = FeeCollector::key(None, program);
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+
+    // Instructions built before fee schedules existed lack the fee schedule and are still accepted.
+    let message = Keypair::new();
+    let mut instruction = instructions::post_message(
+        *program,
+        payer.pubkey(),
+        emitter.pubkey(),
+        message.pubkey(),
+        nonce,
+        [0u8; 32].to_vec(),
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    assert_eq!(
+        instruction.accounts.pop().unwrap().pubkey,
+        fee_schedule_key(program)
+    );
+    common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 10_000),
+            instruction,
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    context.seq.next(emitter.pubkey().to_bytes());
+
+    // Accounts after the fee schedule are ignored.
+    let message = Keypair::new();
+    let mut instruction = instructions::post_message(
+        *program,
+        payer.pubkey(),
+        emitter.pubkey(),
+        message.pubkey(),
+        nonce,
+        [0u8; 32].to_vec(),
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(Pubkey::new_unique(), false));
+    common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 10_000),
+            instruction,
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    context.seq.next(emitter.pubkey().to_bytes());
+}
+
 #[tokio::test]
 async fn guardian_set_change() {
     // Initialize a wormhole bridge on Solana to test with.
@@ -910,32 +1004,24 @@ async fn guardian_set_change_fails() {
 }
 
 #[tokio::test]
-async fn set_fees() {
+async fn expire_guardian_set() {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
     let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
-
     let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadSetMessageFee {
-        fee: U256::from(100u128),
-    }
-    .try_to_vec()
-    .unwrap();
 
-    let message_key = common::post_message(
-        client,
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
         program,
-        payer,
-        &emitter,
-        None,
-        nonce,
-        message.clone(),
-        10_000,
-    )
-    .await
-    .unwrap();
+    );
 
+    // The active guardian set cannot be expired.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadExpireGuardianSet {
+        guardian_set_index: 0,
+    }
+    .try_to_vec()
+    .unwrap();
     let (vaa, body, _body_hash) =
         common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
     let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
@@ -944,184 +1030,375 @@ async fn set_fees() {
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-    common::set_fees(
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    assert!(common::expire_guardian_set(
         client,
         program,
         payer,
         message_key,
         emitter.pubkey(),
         sequence,
-    )
-    .await
-    .unwrap();
-    common::sync(client, payer).await;
-
-    // Fetch Bridge to check on-state value.
-    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
-    let fee_collector = FeeCollector::key(None, program);
-    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
-    assert_eq!(bridge.config.fee, 100);
-
-    // Check that posting a new message fails with too small a fee.
-    let account_balance = common::get_account_balance(client, fee_collector).await;
-    let emitter = Keypair::new();
-    let nonce = rand::thread_rng().gen();
-    let message = [0u8; 32].to_vec();
-    assert!(common::post_message(
-        client,
-        program,
-        payer,
-        &emitter,
-        None,
-        nonce,
-        message.clone(),
-        50
+        0,
     )
     .await
     .is_err());
-    common::sync(client, payer).await;
-
-    assert_eq!(
-        common::get_account_balance(client, fee_collector).await,
-        account_balance,
-    );
 
-    // And succeeds with the new.
-    let emitter = Keypair::new();
+    // Upgrade the guardian set, leaving the old set valid for its expiration period.
+    let (new_public_keys, new_secret_keys) = common::generate_keys(1);
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
-    let nonce = rand::thread_rng().gen();
-    let message = [0u8; 32].to_vec();
-    let _message_key = common::post_message(
-        client,
-        program,
-        payer,
-        &emitter,
-        None,
-        nonce,
-        message.clone(),
-        100,
-    )
-    .await
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 1,
+        new_guardian_set: new_public_keys.clone(),
+    }
+    .try_to_vec()
     .unwrap();
-
     let (vaa, body, _body_hash) =
         common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
     let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
         .await
         .unwrap();
-    let vaa_time = vaa.timestamp;
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
     let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
         &PostedVAADerivationData {
             payload_hash: body.to_vec(),
         },
         program,
     );
-    common::post_vaa(client, program, payer, signature_set, vaa)
-        .await
-        .unwrap();
-    common::sync(client, payer).await;
-
-    // Verify that the fee collector was paid.
-    assert_eq!(
-        common::get_account_balance(client, fee_collector).await,
-        account_balance + 100,
-    );
-
-    // And that the new message is on chain.
-    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
-    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
-
-    // Verify on chain Message
-    assert_eq!(posted_message.message.vaa_version, 0);
-    assert_eq!(
-        posted_message.message.consistency_level,
-        ConsistencyLevel::Confirmed as u8
-    );
-    assert_eq!(posted_message.message.vaa_time, vaa_time);
-    assert_eq!(posted_message.message.vaa_signature_account, signature_set);
-    assert_eq!(posted_message.message.nonce, nonce);
-    assert_eq!(posted_message.message.sequence, sequence);
-    assert_eq!(posted_message.message.emitter_chain, 1);
-    assert_eq!(
-        &posted_message.message.emitter_address,
-        emitter.pubkey().as_ref()
-    );
-    assert_eq!(posted_message.message.payload, message);
-    assert_eq!(
-        posted_message.message.emitter_address,
-        emitter.pubkey().to_bytes()
-    );
-
-    // Verify on chain Signatures
-    assert_eq!(signatures.hash, body);
-    assert_eq!(signatures.guardian_set_index, 0);
-
-    for (signature, _secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
-        assert!(*signature);
-    }
-}
-
-#[tokio::test]
-async fn set_fees_fails() {
-    // Initialize a wormhole bridge on Solana to test with.
-    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-
-    // Use a random key to confirm only the governance key is respected.
-    let emitter = Keypair::new();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
-
-    let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadSetMessageFee {
-        fee: U256::from(100u128),
-    }
-    .try_to_vec()
-    .unwrap();
-
-    let message_key = common::post_message(
+    common::upgrade_guardian_set(
         client,
         program,
         payer,
-        &emitter,
-        None,
-        nonce,
-        message.clone(),
-        10_000,
+        message_key,
+        emitter.pubkey(),
+        0,
+        1,
+        sequence,
     )
     .await
     .unwrap();
 
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    let upgrade_expiration_time = guardian_set.expiration_time;
+    assert_ne!(upgrade_expiration_time, 0);
+
+    // Expire the old guardian set with a VAA from the new one.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadExpireGuardianSet {
+        guardian_set_index: 0,
+    }
+    .try_to_vec()
+    .unwrap();
     let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
-    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
-        .await
-        .unwrap();
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 1, 1);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &new_secret_keys, 1)
+            .await
+            .unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-    assert!(common::set_fees(
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::expire_guardian_set(
         client,
         program,
         payer,
         message_key,
         emitter.pubkey(),
         sequence,
+        0,
     )
     .await
-    .is_err());
+    .unwrap();
     common::sync(client, payer).await;
+
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    assert_ne!(guardian_set.expiration_time, 0);
+    assert!(guardian_set.expiration_time < upgrade_expiration_time);
+
+    // VAAs signed by the expired guardian set can no longer be posted.
+    let message_emitter = Keypair::new();
+    let sequence = context.seq.next(message_emitter.pubkey().to_bytes());
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&message_emitter, vec![0u8; 32], nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    assert!(common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .is_err());
 }
 
 #[tokio::test]
-async fn free_fees() {
+async fn block_signature_set() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let nonce = rand::thread_rng().gen();
+
+    // Verify the signatures of a VAA without posting it yet.
+    let message_emitter = Keypair::new();
+    let sequence = context.seq.next(message_emitter.pubkey().to_bytes());
+    let (message_vaa, message_body, _body_hash) =
+        common::generate_vaa(&message_emitter, vec![0u8; 32], nonce, sequence, 0, 1);
+    let blocked =
+        common::verify_signatures(client, program, payer, message_body, &context.secret, 0)
+            .await
+            .unwrap();
+
+    // Block the signature set through governance.
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadBlockSignatureSet {
+        signature_set: blocked,
+    }
+    .try_to_vec()
+    .unwrap();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::block_signature_set(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        blocked,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let signatures: SignatureSetData = common::get_account_data(client, blocked).await;
+    assert!(signatures.blocked);
+
+    // The governance VAA cannot be replayed.
+    assert!(common::block_signature_set(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        blocked,
+    )
+    .await
+    .is_err());
+
+    // The blocked signature set can no longer post its VAA.
+    assert!(
+        common::post_vaa(client, program, payer, blocked, message_vaa.clone())
+            .await
+            .is_err()
+    );
+
+    // Signatures verified into another signature set are unaffected.
+    let signature_set =
+        common::verify_signatures(client, program, payer, message_body, &context.secret, 0)
+            .await
+            .unwrap();
+    common::post_vaa(client, program, payer, signature_set, message_vaa)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn legacy_blocked_signature_sets() {
+    // A signature set at one of the addresses blocked on mainnet before governance could block
+    // signature sets, holding a full set of signatures.
+    let blocked: Pubkey = "18eK1799CaNMGCUnnCt1Kq2uwKkax6T2WmtrDsZuVFQ"
+        .parse()
+        .unwrap();
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, vec![0u8; 32], nonce, 0, 0, 1);
+    let signatures = SignatureSetData {
+        signatures: vec![true; 6],
+        hash: body,
+        guardian_set_index: 0,
+        payer: None,
+        blocked: false,
+    };
+
+    let (ref mut context, ref mut client, ref payer, ref program) =
+        initialize_with_accounts(vec![(
+            blocked,
+            borsh::BorshSerialize::try_to_vec(&signatures).unwrap(),
+        )])
+        .await;
+
+    // No governance VAA blocked it, yet it still cannot post its VAA.
+    let signatures: SignatureSetData = common::get_account_data(client, blocked).await;
+    assert!(!signatures.blocked);
+    assert!(
+        common::post_vaa(client, program, payer, blocked, vaa.clone())
+            .await
+            .is_err()
+    );
+
+    // Signatures verified into another signature set are unaffected.
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn block_legacy_signature_set() {
+    // A signature set created before payers were recorded, without the trailer the block is kept
+    // in, holding a full set of signatures.
+    let blocked = Keypair::new().pubkey();
+    let message_emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let (message_vaa, message_body, _body_hash) =
+        common::generate_vaa(&message_emitter, vec![0u8; 32], nonce, 0, 0, 1);
+    let signatures = SignatureSetData {
+        signatures: vec![true; 6],
+        hash: message_body,
+        guardian_set_index: 0,
+        payer: None,
+        blocked: false,
+    };
+    let legacy_len = borsh::BorshSerialize::try_to_vec(&signatures)
+        .unwrap()
+        .len();
+
+    let (ref mut context, ref mut client, ref payer, ref program) =
+        initialize_with_accounts(vec![(
+            blocked,
+            borsh::BorshSerialize::try_to_vec(&signatures).unwrap(),
+        )])
+        .await;
+
+    // Block the signature set through governance, which grows it to hold the block.
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadBlockSignatureSet {
+        signature_set: blocked,
+    }
+    .try_to_vec()
+    .unwrap();
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::block_signature_set(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        blocked,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let account = client.get_account(blocked).await.unwrap().unwrap();
+    assert!(account.data.len() > legacy_len);
+    let signatures: SignatureSetData = common::get_account_data(client, blocked).await;
+    assert!(signatures.blocked);
+    assert_eq!(signatures.payer, None);
+    assert_eq!(signatures.signatures, vec![true; 6]);
+
+    // The blocked signature set can no longer post its VAA.
+    assert!(
+        common::post_vaa(client, program, payer, blocked, message_vaa)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn legacy_expired_guardian_set() {
+    // The initial mainnet guardian set was never expired by an upgrade, only its creation time
+    // marks it as expired.
+    for (creation_time, expired) in [(1628099186, true), (1628099187, false)] {
+        let (public_keys, secret_keys) = common::generate_keys(6);
+        let program = common::program_id();
+        let bridge = BridgeData {
+            guardian_set_index: 0,
+            last_lamports: 0,
+            config: BridgeConfig {
+                guardian_set_expiration_time: 2_000_000_000,
+                fee: 500,
+            },
+        };
+        let guardian_set = GuardianSetData {
+            index: 0,
+            keys: public_keys,
+            creation_time,
+            expiration_time: 0,
+        };
+        let (ref mut client, ref payer, ref program) = common::setup_with_accounts(vec![
+            (
+                Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program),
+                borsh::BorshSerialize::try_to_vec(&bridge).unwrap(),
+            ),
+            (
+                GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+                    &GuardianSetDerivationData { index: 0 },
+                    &program,
+                ),
+                borsh::BorshSerialize::try_to_vec(&guardian_set).unwrap(),
+            ),
+        ])
+        .await;
+
+        let emitter = Keypair::new();
+        let nonce = rand::thread_rng().gen();
+        let (vaa, body, _body_hash) = common::generate_vaa(&emitter, vec![0u8; 32], nonce, 0, 0, 1);
+        let result =
+            common::post_vaa_with_signatures(client, program, payer, body, &secret_keys[..5], vaa)
+                .await;
+        assert_eq!(result.is_err(), expired);
+    }
+}
+
+#[tokio::test]
+async fn set_fees() {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
     let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
-    // Set Fees to 0.
     let nonce = rand::thread_rng().gen();
     let message = GovernancePayloadSetMessageFee {
-        fee: U256::from(0u128),
+        fee: U256::from(100u128),
     }
     .try_to_vec()
     .unwrap();
@@ -1163,11 +1440,34 @@ async fn free_fees() {
     let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
     let fee_collector = FeeCollector::key(None, program);
     let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
-    assert_eq!(bridge.config.fee, 0);
+    assert_eq!(bridge.config.fee, 100);
 
-    // Check that posting a new message is free.
+    // Check that posting a new message fails with too small a fee.
     let account_balance = common::get_account_balance(client, fee_collector).await;
     let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    assert!(common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        50
+    )
+    .await
+    .is_err());
+    common::sync(client, payer).await;
+
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        account_balance,
+    );
+
+    // And succeeds with the new.
+    let emitter = Keypair::new();
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
     let nonce = rand::thread_rng().gen();
     let message = [0u8; 32].to_vec();
@@ -1179,7 +1479,7 @@ async fn free_fees() {
         None,
         nonce,
         message.clone(),
-        0,
+        100,
     )
     .await
     .unwrap();
@@ -1204,7 +1504,7 @@ async fn free_fees() {
     // Verify that the fee collector was paid.
     assert_eq!(
         common::get_account_balance(client, fee_collector).await,
-        account_balance,
+        account_balance + 100,
     );
 
     // And that the new message is on chain.
@@ -1242,86 +1542,71 @@ async fn free_fees() {
 }
 
 #[tokio::test]
-async fn transfer_fees() {
+async fn message_fee_schedule() {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
-
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
     let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadTransferFees {
-        amount: 100u128.into(),
-        to: payer.pubkey().to_bytes(),
-    }
-    .try_to_vec()
-    .unwrap();
-
-    // Fetch accounts for chain state checking.
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
     let fee_collector = FeeCollector::key(None, program);
 
-    let message_key = common::post_message(
-        client,
-        program,
-        payer,
-        &emitter,
-        None,
-        nonce,
-        message.clone(),
-        10_000,
-    )
-    .await
-    .unwrap();
+    let fee_schedule_key = fee_schedule_key(program);
+
+    // Bridges start without a fee schedule, so without a per byte fee.
+    assert!(client
+        .get_account(fee_schedule_key)
+        .await
+        .unwrap()
+        .is_none());
 
+    // Charge a base fee of 100 plus 2 per byte of payload.
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let message = GovernancePayloadSetMessageFeeSchedule {
+        fee: U256::from(100u128),
+        fee_per_byte: U256::from(2u128),
+    }
+    .try_to_vec()
+    .unwrap();
     let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+        common::generate_vaa(&governance, message.clone(), nonce, sequence, 0, 1);
     let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
         .await
         .unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-
-    let previous_balance = common::get_account_balance(client, fee_collector).await;
-
-    common::transfer_fees(
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::set_message_fee_schedule(
         client,
         program,
         payer,
         message_key,
-        emitter.pubkey(),
-        payer.pubkey(),
+        governance.pubkey(),
         sequence,
     )
     .await
     .unwrap();
     common::sync(client, payer).await;
-    assert_eq!(
-        common::get_account_balance(client, fee_collector).await,
-        previous_balance - 100
-    );
-}
 
-#[tokio::test]
-async fn transfer_fees_fails() {
-    // Initialize a wormhole bridge on Solana to test with.
-    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    // The bridge config keeps its layout, the per byte fee is stored in the fee schedule.
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.fee, 100);
+    let bridge_account = client.get_account(bridge_key).await.unwrap().unwrap();
+    assert_eq!(bridge_account.data.len(), 20);
+    let fee_schedule: FeeScheduleData = common::get_account_data(client, fee_schedule_key).await;
+    assert_eq!(fee_schedule.fee_per_byte, 2);
+    assert!(fee_schedule.overrides.is_empty());
 
-    // Use an invalid emitter.
+    // A 32 byte message costs 164 lamports, both reliable and unreliable.
+    let account_balance = common::get_account_balance(client, fee_collector).await;
     let emitter = Keypair::new();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
-
-    let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadTransferFees {
-        amount: 100u128.into(),
-        to: payer.pubkey().to_bytes(),
-    }
-    .try_to_vec()
-    .unwrap();
-
-    // Fetch accounts for chain state checking.
-    let fee_collector = FeeCollector::key(None, program);
-
-    let message_key = common::post_message(
+    let message = [0u8; 32].to_vec();
+    assert!(common::post_message(
         client,
         program,
         payer,
@@ -1329,59 +1614,23 @@ async fn transfer_fees_fails() {
         None,
         nonce,
         message.clone(),
-        10_000,
+        163,
     )
     .await
-    .unwrap();
-
-    let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
-    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
-        .await
-        .unwrap();
-    common::post_vaa(client, program, payer, signature_set, vaa)
-        .await
-        .unwrap();
-
-    let previous_balance = common::get_account_balance(client, fee_collector).await;
-
-    assert!(common::transfer_fees(
+    .is_err());
+    assert!(common::post_message_unreliable(
         client,
         program,
         payer,
-        message_key,
-        emitter.pubkey(),
-        payer.pubkey(),
-        sequence,
+        &emitter,
+        &Keypair::new(),
+        nonce,
+        message.clone(),
+        163,
     )
     .await
     .is_err());
-    common::sync(client, payer).await;
-    assert_eq!(
-        common::get_account_balance(client, fee_collector).await,
-        previous_balance
-    );
-}
-
-#[tokio::test]
-async fn transfer_too_much() {
-    // Initialize a wormhole bridge on Solana to test with.
-    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
-
-    let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadTransferFees {
-        amount: 100_000_000_000u64.into(),
-        to: payer.pubkey().to_bytes(),
-    }
-    .try_to_vec()
-    .unwrap();
-
-    // Fetch accounts for chain state checking.
-    let fee_collector = FeeCollector::key(None, program);
-
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -1389,112 +1638,293 @@ async fn transfer_too_much() {
         None,
         nonce,
         message.clone(),
-        10_000,
+        164,
     )
     .await
     .unwrap();
+    context.seq.next(emitter.pubkey().to_bytes());
+    common::sync(client, payer).await;
+
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        account_balance + 164,
+    );
 
+    // Override the fee for the emitter.
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let message = GovernancePayloadSetEmitterMessageFee {
+        emitter: emitter.pubkey(),
+        fee: U256::from(10u128),
+        fee_per_byte: U256::from(0u128),
+    }
+    .try_to_vec()
+    .unwrap();
     let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+        common::generate_vaa(&governance, message.clone(), nonce, sequence, 0, 1);
     let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
         .await
         .unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-
-    let previous_balance = common::get_account_balance(client, fee_collector).await;
-
-    // Should fail to transfer.
-    assert!(common::transfer_fees(
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::set_emitter_message_fee(
         client,
         program,
         payer,
         message_key,
-        emitter.pubkey(),
-        payer.pubkey(),
+        governance.pubkey(),
         sequence,
+        emitter.pubkey(),
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let fee_schedule: FeeScheduleData = common::get_account_data(client, fee_schedule_key).await;
+    assert_eq!(fee_schedule.fee_per_byte, 2);
+    assert_eq!(fee_schedule.overrides.len(), 1);
+    assert_eq!(fee_schedule.overrides[0].emitter, emitter.pubkey());
+    assert_eq!(
+        fee_schedule.overrides[0].fee,
+        MessageFee {
+            base: 10,
+            per_byte: 0
+        }
+    );
+
+    // The sequence tracker keeps its sequence, and now requires the fee schedule.
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter.pubkey(),
+        },
+        program,
+    );
+    let tracker: SequenceTracker = common::get_account_data(client, sequence_key).await;
+    assert_eq!(tracker.sequence, 1);
+    assert!(tracker.fee_schedule_required);
+
+    // The emitter now pays its override, while other emitters still pay the schedule.
+    let account_balance = common::get_account_balance(client, fee_collector).await;
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10,
+    )
+    .await
+    .unwrap();
+    assert!(common::post_message(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        None,
+        nonce,
+        [0u8; 32].to_vec(),
+        10,
     )
     .await
     .is_err());
     common::sync(client, payer).await;
+
     assert_eq!(
         common::get_account_balance(client, fee_collector).await,
-        previous_balance
+        account_balance + 10,
     );
 }
 
 #[tokio::test]
-async fn foreign_bridge_messages() {
+async fn post_message_without_fee_schedule() {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
     let nonce = rand::thread_rng().gen();
-    let message = [0u8; 32].to_vec();
+    let fee_collector = FeeCollector::key(None, program);
     let emitter = Keypair::new();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = [0u8; 32].to_vec();
 
-    // Verify the VAA generated on a foreign chain.
-    let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 2);
+    // Callers built before fee schedules pay the flat fee.
+    assert!(common::post_message_without_fee_schedule(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        499,
+    )
+    .await
+    .is_err());
+    let account_balance = common::get_account_balance(client, fee_collector).await;
+    common::post_message_without_fee_schedule(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        500,
+    )
+    .await
+    .unwrap();
+    context.seq.next(emitter.pubkey().to_bytes());
+    common::sync(client, payer).await;
 
-    // Derive where we expect created accounts to be.
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        account_balance + 500,
+    );
+
+    // Charge a base fee of 100 plus 2 per byte of payload.
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let payload = GovernancePayloadSetMessageFeeSchedule {
+        fee: U256::from(100u128),
+        fee_per_byte: U256::from(2u128),
+    }
+    .try_to_vec()
+    .unwrap();
+    let (vaa, body, _body_hash) = common::generate_vaa(&governance, payload, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
     let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
         &PostedVAADerivationData {
             payload_hash: body.to_vec(),
         },
         program,
     );
+    common::set_message_fee_schedule(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    // Without the fee schedule only the new flat fee is charged.
+    let account_balance = common::get_account_balance(client, fee_collector).await;
+    common::post_message_without_fee_schedule(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        100,
+    )
+    .await
+    .unwrap();
+    context.seq.next(emitter.pubkey().to_bytes());
+    common::sync(client, payer).await;
+
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        account_balance + 100,
+    );
 
+    // Give the emitter its own fee, after which it must pass the fee schedule.
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let payload = GovernancePayloadSetEmitterMessageFee {
+        emitter: emitter.pubkey(),
+        fee: U256::from(10u128),
+        fee_per_byte: U256::from(1u128),
+    }
+    .try_to_vec()
+    .unwrap();
+    let (vaa, body, _body_hash) = common::generate_vaa(&governance, payload, nonce, sequence, 0, 1);
     let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
         .await
         .unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-    common::sync(client, payer).await;
-
-    // Fetch chain accounts to verify state.
-    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
-    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
-
-    assert_eq!(posted_message.message.vaa_version, 0);
-    assert_eq!(posted_message.message.vaa_signature_account, signature_set);
-    assert_eq!(posted_message.message.nonce, nonce);
-    assert_eq!(posted_message.message.sequence, sequence);
-    assert_eq!(posted_message.message.emitter_chain, 2);
-    assert_eq!(posted_message.message.payload, message);
-    assert_eq!(
-        posted_message.message.emitter_address,
-        emitter.pubkey().to_bytes()
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
     );
+    common::set_emitter_message_fee(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+        emitter.pubkey(),
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
 
-    // Verify on chain Signatures
-    assert_eq!(signatures.hash, body);
-    assert_eq!(signatures.guardian_set_index, 0);
+    assert!(common::post_message_without_fee_schedule(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .is_err());
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        42,
+    )
+    .await
+    .unwrap();
+    context.seq.next(emitter.pubkey().to_bytes());
 
-    for (signature, _secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
-        assert!(*signature);
-    }
+    // Other emitters still post without it.
+    common::post_message_without_fee_schedule(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        nonce,
+        message,
+        100,
+    )
+    .await
+    .unwrap();
 }
 
 #[tokio::test]
-async fn transfer_total_fails() {
+async fn set_fees_fails() {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
-
-    // Be sure any previous tests have fully committed.
-    common::sync(client, payer).await;
 
-    let fee_collector = FeeCollector::key(None, program);
-    let account_balance = common::get_account_balance(client, fee_collector).await;
+    // Use a random key to confirm only the governance key is respected.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
-    // Prepare to remove total balance, adding 10_000 to include the fee we're about to pay.
     let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadTransferFees {
-        amount: (account_balance + 10_000).into(),
-        to: payer.pubkey().to_bytes(),
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100u128),
     }
     .try_to_vec()
     .unwrap();
@@ -1520,26 +1950,1037 @@ async fn transfer_total_fails() {
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-
-    // Transferring total fees should fail, to prevent the account being de-allocated.
-    assert!(common::transfer_fees(
+    assert!(common::set_fees(
         client,
         program,
         payer,
         message_key,
         emitter.pubkey(),
-        payer.pubkey(),
         sequence,
     )
     .await
     .is_err());
     common::sync(client, payer).await;
+}
 
-    // The fee should have been paid, but other than that the balance should be exactly the same,
-    // I.E non-zero.
-    assert_eq!(
-        common::get_account_balance(client, fee_collector).await,
-        account_balance + 10_000
+#[tokio::test]
+async fn free_fees() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Set Fees to 0.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(0u128),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    // Fetch Bridge to check on-state value.
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let fee_collector = FeeCollector::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.fee, 0);
+
+    // Check that posting a new message is free.
+    let account_balance = common::get_account_balance(client, fee_collector).await;
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let _message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let vaa_time = vaa.timestamp;
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    // Verify that the fee collector was paid.
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        account_balance,
+    );
+
+    // And that the new message is on chain.
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+
+    // Verify on chain Message
+    assert_eq!(posted_message.message.vaa_version, 0);
+    assert_eq!(
+        posted_message.message.consistency_level,
+        ConsistencyLevel::Confirmed as u8
+    );
+    assert_eq!(posted_message.message.vaa_time, vaa_time);
+    assert_eq!(posted_message.message.vaa_signature_account, signature_set);
+    assert_eq!(posted_message.message.nonce, nonce);
+    assert_eq!(posted_message.message.sequence, sequence);
+    assert_eq!(posted_message.message.emitter_chain, 1);
+    assert_eq!(
+        &posted_message.message.emitter_address,
+        emitter.pubkey().as_ref()
+    );
+    assert_eq!(posted_message.message.payload, message);
+    assert_eq!(
+        posted_message.message.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+
+    // Verify on chain Signatures
+    assert_eq!(signatures.hash, body);
+    assert_eq!(signatures.guardian_set_index, 0);
+
+    for (signature, _secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
+        assert!(*signature);
+    }
+}
+
+#[tokio::test]
+async fn transfer_fees() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadTransferFees {
+        amount: 100u128.into(),
+        to: payer.pubkey().to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // Fetch accounts for chain state checking.
+    let fee_collector = FeeCollector::key(None, program);
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    let previous_balance = common::get_account_balance(client, fee_collector).await;
+
+    common::transfer_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        payer.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        previous_balance - 100
+    );
+}
+
+#[tokio::test]
+async fn transfer_fees_fails() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    // Use an invalid emitter.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadTransferFees {
+        amount: 100u128.into(),
+        to: payer.pubkey().to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // Fetch accounts for chain state checking.
+    let fee_collector = FeeCollector::key(None, program);
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    let previous_balance = common::get_account_balance(client, fee_collector).await;
+
+    assert!(common::transfer_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        payer.pubkey(),
+        sequence,
+    )
+    .await
+    .is_err());
+    common::sync(client, payer).await;
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        previous_balance
+    );
+}
+
+#[tokio::test]
+async fn transfer_too_much() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadTransferFees {
+        amount: 100_000_000_000u64.into(),
+        to: payer.pubkey().to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // Fetch accounts for chain state checking.
+    let fee_collector = FeeCollector::key(None, program);
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    let previous_balance = common::get_account_balance(client, fee_collector).await;
+
+    // Should fail to transfer.
+    assert!(common::transfer_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        payer.pubkey(),
+        sequence,
+    )
+    .await
+    .is_err());
+    common::sync(client, payer).await;
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        previous_balance
+    );
+}
+
+#[tokio::test]
+async fn foreign_bridge_messages() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Verify the VAA generated on a foreign chain.
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 2);
+
+    // Derive where we expect created accounts to be.
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    // Fetch chain accounts to verify state.
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+
+    assert_eq!(posted_message.message.vaa_version, 0);
+    assert_eq!(posted_message.message.vaa_signature_account, signature_set);
+    assert_eq!(posted_message.message.nonce, nonce);
+    assert_eq!(posted_message.message.sequence, sequence);
+    assert_eq!(posted_message.message.emitter_chain, 2);
+    assert_eq!(posted_message.message.payload, message);
+    assert_eq!(
+        posted_message.message.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+
+    // Verify on chain Signatures
+    assert_eq!(signatures.hash, body);
+    assert_eq!(signatures.guardian_set_index, 0);
+
+    for (signature, _secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
+        assert!(*signature);
+    }
+}
+
+#[tokio::test]
+async fn transfer_total_fails() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Be sure any previous tests have fully committed.
+    common::sync(client, payer).await;
+
+    let fee_collector = FeeCollector::key(None, program);
+    let account_balance = common::get_account_balance(client, fee_collector).await;
+
+    // Prepare to remove total balance, adding 10_000 to include the fee we're about to pay.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadTransferFees {
+        amount: (account_balance + 10_000).into(),
+        to: payer.pubkey().to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // Transferring total fees should fail, to prevent the account being de-allocated.
+    assert!(common::transfer_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        payer.pubkey(),
+        sequence,
+    )
+    .await
+    .is_err());
+    common::sync(client, payer).await;
+
+    // The fee should have been paid, but other than that the balance should be exactly the same,
+    // I.E non-zero.
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        account_balance + 10_000
+    );
+}
+
+#[tokio::test]
+async fn post_vaa_with_signatures() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 2);
+
+    // Derive where we expect created accounts to be.
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+
+    // Four of six guardians do not reach quorum.
+    assert!(common::post_vaa_with_signatures(
+        client,
+        program,
+        payer,
+        body,
+        &context.secret[..4],
+        vaa.clone(),
+    )
+    .await
+    .is_err());
+
+    // Repeating a guardian's signature does not count towards quorum either.
+    let repeated = [
+        context.secret[0],
+        context.secret[0],
+        context.secret[1],
+        context.secret[2],
+        context.secret[3],
+    ];
+    assert!(
+        common::post_vaa_with_signatures(client, program, payer, body, &repeated, vaa.clone())
+            .await
+            .is_err()
+    );
+
+    // Signatures over a different message are ignored.
+    let (_, other_body, _) = common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 3);
+    assert!(common::post_vaa_with_signatures(
+        client,
+        program,
+        payer,
+        other_body,
+        &context.secret[1..],
+        vaa.clone(),
+    )
+    .await
+    .is_err());
+
+    // Five guardians reach quorum, posting the VAA in a single transaction. Other secp instructions
+    // in the transaction are ignored, even ones the bridge cannot parse because they verify
+    // signatures held by another instruction.
+    let mut unrelated = common::secp256k1_instruction(&context.secret[1..], &body, 1);
+    unrelated.data.truncate(12);
+    unrelated.data[0] = 1;
+    common::post_vaa_with_signatures_after(
+        client,
+        program,
+        payer,
+        vec![unrelated],
+        body,
+        &context.secret[1..],
+        vaa,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    // Fetch chain accounts to verify state.
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+
+    assert_eq!(posted_message.message.vaa_version, 0);
+    assert_eq!(
+        posted_message.message.vaa_signature_account,
+        Pubkey::default()
+    );
+    assert_eq!(posted_message.message.nonce, nonce);
+    assert_eq!(posted_message.message.sequence, sequence);
+    assert_eq!(posted_message.message.emitter_chain, 2);
+    assert_eq!(posted_message.message.payload, message);
+    assert_eq!(
+        posted_message.message.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+    let posted_payer: PostedVAAPayerData =
+        common::get_account_data(client, posted_vaa_payer_key(program, &message_key)).await;
+    assert_eq!(posted_payer.payer, payer.pubkey());
+    assert!(posted_payer.post_time > 0);
+}
+
+#[tokio::test]
+async fn close_accounts() {
+    // A VAA posted long enough ago to be closed, by a payer other than the one running the tests.
+    let authority = Keypair::new();
+    let old_emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let (old_vaa, old_body, _body_hash) =
+        common::generate_vaa(&old_emitter, vec![0u8; 32], nonce, 0, 0, 1);
+
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize_with_accounts(
+        common::posted_vaa_accounts(&old_vaa, old_body, authority.pubkey(), 0),
+    )
+    .await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100u128),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // Post a VAA, both accounts record who paid for them, the VAA in a separate account along
+    // with the time it was posted.
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    let posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+    let posted_payer: PostedVAAPayerData =
+        common::get_account_data(client, posted_vaa_payer_key(program, &posted_vaa)).await;
+    assert_eq!(signatures.payer.unwrap().payer, payer.pubkey());
+    assert_eq!(posted_payer.payer, payer.pubkey());
+    assert!(posted_payer.post_time > 0);
+
+    // Only the payer can close them.
+    let other = Keypair::new();
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &other.pubkey(), 1_000_000_000),
+            system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), 1_000_000_000),
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    let old_posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: old_body.to_vec(),
+        },
+        program,
+    );
+    assert!(
+        common::close_posted_vaa(client, program, payer, old_posted_vaa)
+            .await
+            .is_err()
+    );
+    assert!(
+        common::close_signature_set(client, program, &other, 0, signature_set)
+            .await
+            .is_err()
+    );
+
+    // Other accounts owned by the bridge cannot be closed as VAAs.
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.pubkey().to_bytes(),
+            emitter_chain: 1,
+            sequence,
+        },
+        program,
+    );
+    assert!(common::close_posted_vaa(client, program, payer, claim)
+        .await
+        .is_err());
+    assert!(
+        common::close_posted_vaa(client, program, payer, signature_set)
+            .await
+            .is_err()
+    );
+
+    // The payer can close the signature set right away, reclaiming the rent.
+    let balance = common::get_account_balance(client, payer.pubkey()).await;
+    common::close_signature_set(client, program, payer, 0, signature_set)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    assert!(client.get_account(signature_set).await.unwrap().is_none());
+    assert!(common::get_account_balance(client, payer.pubkey()).await > balance);
+
+    // The payer of the old VAA can close it, reclaiming the rent.
+    let balance = common::get_account_balance(client, authority.pubkey()).await;
+    common::close_posted_vaa(client, program, &authority, old_posted_vaa)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    assert!(client
+        .get_account(posted_vaa_payer_key(program, &old_posted_vaa))
+        .await
+        .unwrap()
+        .is_none());
+    assert!(common::get_account_balance(client, authority.pubkey()).await > balance);
+
+    // The VAA is left behind empty, holding only enough lamports to stay rent exempt.
+    let closed = client.get_account(old_posted_vaa).await.unwrap().unwrap();
+    assert!(closed.data.is_empty());
+    assert_eq!(closed.owner, *program);
+    assert_eq!(closed.lamports, Rent::default().minimum_balance(0));
+
+    // A closed VAA cannot be closed again, nor posted again through either instruction.
+    assert!(
+        common::close_posted_vaa(client, program, &authority, old_posted_vaa)
+            .await
+            .is_err()
+    );
+
+    let signature_set =
+        common::verify_signatures(client, program, payer, old_body, &context.secret, 0)
+            .await
+            .unwrap();
+    assert!(
+        common::post_vaa(client, program, payer, signature_set, old_vaa.clone())
+            .await
+            .is_err()
+    );
+    assert!(common::post_vaa_with_signatures(
+        client,
+        program,
+        payer,
+        old_body,
+        &context.secret[..5],
+        old_vaa
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn close_posted_vaa_before_redeem() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100u128),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // Anyone can post a VAA and be recorded as its payer.
+    let front_runner = Keypair::new();
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &front_runner.pubkey(),
+            1_000_000_000,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    common::post_vaa_with_signatures(
+        client,
+        program,
+        &front_runner,
+        body,
+        &context.secret[..5],
+        vaa,
+    )
+    .await
+    .unwrap();
+
+    // Closing the VAA right away would keep it from ever being redeemed, as it could not be posted
+    // again, so even its payer must wait before closing it.
+    let posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    assert!(
+        common::close_posted_vaa(client, program, &front_runner, posted_vaa)
+            .await
+            .is_err()
+    );
+    common::sync(client, payer).await;
+
+    let posted_payer: PostedVAAPayerData =
+        common::get_account_data(client, posted_vaa_payer_key(program, &posted_vaa)).await;
+    assert_eq!(posted_payer.payer, front_runner.pubkey());
+
+    // The VAA can still be redeemed.
+    common::set_fees(
+        client,
+        program,
+        payer,
+        posted_vaa,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.fee, 100);
+}
+
+#[tokio::test]
+async fn close_legacy_accounts() {
+    // A signature set created before payers were recorded, without the payer trailer.
+    let legacy_signature_set = Keypair::new();
+    let legacy_signatures = SignatureSetData {
+        signatures: vec![true; 6],
+        hash: [7u8; 32],
+        guardian_set_index: 0,
+        payer: None,
+        blocked: false,
+    };
+
+    let (ref mut context, ref mut client, ref payer, ref program) =
+        initialize_with_accounts(vec![(
+            legacy_signature_set.pubkey(),
+            borsh::BorshSerialize::try_to_vec(&legacy_signatures).unwrap(),
+        )])
+        .await;
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Post a VAA the way clients built before payers were recorded do, without the account
+    // recording the payer.
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+
+    let posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    let mut post_vaa = instructions::post_vaa(*program, payer.pubkey(), signature_set, vaa);
+    let posted_vaa_payer = post_vaa.accounts.pop().unwrap();
+    assert_eq!(
+        posted_vaa_payer.pubkey,
+        posted_vaa_payer_key(program, &posted_vaa)
+    );
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[post_vaa],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    // The VAA is posted with its usual layout, but without a record of its payer nobody can close
+    // it, not even the account that paid for it.
+    let posted_message: PostedVAAData = common::get_account_data(client, posted_vaa).await;
+    assert_eq!(posted_message.message.payload, message);
+    assert!(client
+        .get_account(posted_vaa_payer_key(program, &posted_vaa))
+        .await
+        .unwrap()
+        .is_none());
+    assert!(common::close_posted_vaa(client, program, payer, posted_vaa)
+        .await
+        .is_err());
+
+    // A legacy signature set can only be closed by signing with its own keypair.
+    assert!(
+        common::close_signature_set(client, program, payer, 0, legacy_signature_set.pubkey())
+            .await
+            .is_err()
+    );
+
+    let mut close = instructions::close_signature_set(
+        *program,
+        payer.pubkey(),
+        0,
+        legacy_signature_set.pubkey(),
+    );
+    close.accounts[2].is_signer = true;
+    let balance = common::get_account_balance(client, payer.pubkey()).await;
+    common::execute(
+        client,
+        payer,
+        &[payer, &legacy_signature_set],
+        &[close],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    assert!(client
+        .get_account(legacy_signature_set.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(common::get_account_balance(client, payer.pubkey()).await > balance);
+}
+
+#[tokio::test]
+async fn bridge_events() {
+    // Initialize a wormhole bridge on Solana to test with, along with a VAA posted long enough ago
+    // to be closed.
+    let authority = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let (old_vaa, old_body, _body_hash) =
+        common::generate_vaa(&Keypair::new(), vec![0u8; 32], nonce, 0, 0, 1);
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize_with_accounts(
+        common::posted_vaa_accounts(&old_vaa, old_body, authority.pubkey(), 0),
+    )
+    .await;
+    let fee_collector = FeeCollector::<'_>::key(None, program);
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100u128),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // Posting a message logs who emitted it and the fee it required.
+    let message_key = Keypair::new();
+    let events = common::execute_with_events(
+        client,
+        payer,
+        &[payer, &emitter, &message_key],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 10_000),
+            instructions::post_message(
+                *program,
+                payer.pubkey(),
+                emitter.pubkey(),
+                message_key.pubkey(),
+                nonce,
+                message.clone(),
+                ConsistencyLevel::Confirmed,
+            )
+            .unwrap(),
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        events,
+        vec![BridgeEvent::MessagePosted {
+            message: message_key.pubkey(),
+            emitter: emitter.pubkey(),
+            sequence,
+            nonce,
+            consistency_level: 1,
+            payload_len: message.len() as u32,
+            fee: 500,
+        }]
+    );
+
+    // Posting the VAA of the message logs where it was stored.
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    let events = common::execute_with_events(
+        client,
+        payer,
+        &[payer],
+        &[instructions::post_vaa(
+            *program,
+            payer.pubkey(),
+            signature_set,
+            vaa,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        events,
+        vec![BridgeEvent::VAAPosted {
+            posted_vaa,
+            guardian_set_index: 0,
+            emitter_chain: 1,
+            emitter_address: emitter.pubkey().to_bytes(),
+            sequence,
+        }]
+    );
+
+    // Executing the governance VAA logs the new fee.
+    let events = common::execute_with_events(
+        client,
+        payer,
+        &[payer],
+        &[instructions::set_fees(
+            *program,
+            payer.pubkey(),
+            posted_vaa,
+            emitter.pubkey(),
+            sequence,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        events,
+        vec![BridgeEvent::MessageFeeChanged {
+            fee: 100,
+            fee_per_byte: None,
+        }]
+    );
+
+    // Closing the accounts logs who received their rent.
+    let old_posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: old_body.to_vec(),
+        },
+        program,
+    );
+    let events = common::execute_with_events(
+        client,
+        payer,
+        &[payer, &authority],
+        &[
+            instructions::close_posted_vaa(*program, authority.pubkey(), old_posted_vaa),
+            instructions::close_signature_set(*program, payer.pubkey(), 0, signature_set),
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            BridgeEvent::PostedVAAClosed {
+                posted_vaa: old_posted_vaa,
+                recipient: authority.pubkey(),
+            },
+            BridgeEvent::SignatureSetClosed {
+                signature_set,
+                recipient: payer.pubkey(),
+            },
+        ]
     );
 }
 
@@ -24,6 +24,10 @@ use crate::{
     },
 };
 use bridge::{
+    accounts::{
+        fee_schedule_meta,
+        message_fee,
+    },
     api::PostMessageData,
     types::ConsistencyLevel,
     vaa::SerializePayload,
@@ -172,14 +176,6 @@ pub fn transfer_native(
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.authority_signer, None)?;
 
-    // Pay fee
-    let transfer_ix = solana_program::system_instruction::transfer(
-        accs.payer.key,
-        accs.fee_collector.key,
-        accs.bridge.config.fee,
-    );
-    invoke(&transfer_ix, ctx.accounts)?;
-
     let metadata: Metadata =
         Metadata::from_account_info(accs.spl_metadata.info()).ok_or(InvalidMetadata)?;
 
@@ -197,15 +193,34 @@ pub fn transfer_native(
         uri: metadata.data.uri,
         token_id: U256::from_big_endian(&accs.mint.info().key.to_bytes()),
     };
+    let payload = payload.try_to_vec()?;
+
+    // Pay fee
+    let bridge_fee = message_fee(
+        &accs.config.wormhole_bridge,
+        &accs.bridge.config,
+        accs.emitter.key,
+        ctx.accounts,
+        payload.len(),
+    )?;
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        bridge_fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
             nonce: data.nonce,
-            payload: payload.try_to_vec()?,
+            payload,
             consistency_level: ConsistencyLevel::Finalized,
         },
     );
 
+    // Bridges deployed before fee schedules don't take the fee schedule, so it is only passed on
+    // when the caller supplied it.
     let ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
@@ -219,7 +234,10 @@ pub fn transfer_native(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
+        ]
+        .into_iter()
+        .chain(fee_schedule_meta(&accs.config.wormhole_bridge, ctx.accounts))
+        .collect(),
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
@@ -312,15 +330,6 @@ pub fn transfer_wrapped(
     )?;
     invoke_seeded(&burn_ix, ctx, &accs.authority_signer, None)?;
 
-    // Pay fee
-    let transfer_ix = solana_program::system_instruction::transfer(
-        accs.payer.key,
-        accs.fee_collector.key,
-        accs.bridge.config.fee,
-    );
-
-    invoke(&transfer_ix, ctx.accounts)?;
-
     // Enfoce wrapped meta to be uninitialized.
     let derivation_data: WrappedMetaDerivationData = (&*accs).into();
     accs.wrapped_meta
@@ -353,15 +362,34 @@ pub fn transfer_wrapped(
         name: metadata.data.name,
         uri: metadata.data.uri,
     };
+    let payload = payload.try_to_vec()?;
+
+    // Pay fee
+    let bridge_fee = message_fee(
+        &accs.config.wormhole_bridge,
+        &accs.bridge.config,
+        accs.emitter.key,
+        ctx.accounts,
+        payload.len(),
+    )?;
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        bridge_fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
             nonce: data.nonce,
-            payload: payload.try_to_vec()?,
+            payload,
             consistency_level: ConsistencyLevel::Finalized,
         },
     );
 
+    // Bridges deployed before fee schedules don't take the fee schedule, so it is only passed on
+    // when the caller supplied it.
     let ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
@@ -375,7 +403,10 @@ pub fn transfer_wrapped(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
+        ]
+        .into_iter()
+        .chain(fee_schedule_meta(&accs.config.wormhole_bridge, ctx.accounts))
+        .collect(),
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
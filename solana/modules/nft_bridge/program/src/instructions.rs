@@ -35,6 +35,7 @@ use crate::{
 use borsh::BorshSerialize;
 use bridge::{
     accounts::{
+        fee_schedule_key,
         Bridge,
         Claim,
         ClaimDerivationData,
@@ -371,6 +372,7 @@ pub fn transfer_native(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(fee_schedule_key(&bridge_id), false),
         ],
         data: (crate::instruction::Instruction::TransferNative, data).try_to_vec()?,
     })
@@ -450,6 +452,7 @@ pub fn transfer_wrapped(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(fee_schedule_key(&bridge_id), false),
         ],
         data: (crate::instruction::Instruction::TransferWrapped, data).try_to_vec()?,
     })
@@ -13,6 +13,10 @@ use crate::{
     TokenBridgeError::*,
 };
 use bridge::{
+    accounts::{
+        fee_schedule_meta,
+        message_fee,
+    },
     api::PostMessageData,
     types::ConsistencyLevel,
     vaa::SerializePayload,
@@ -93,15 +97,6 @@ pub fn attest_token(
     accs: &mut AttestToken,
     data: AttestTokenData,
 ) -> Result<()> {
-    // Pay fee
-    let transfer_ix = solana_program::system_instruction::transfer(
-        accs.payer.key,
-        accs.fee_collector.key,
-        accs.bridge.config.fee,
-    );
-
-    invoke(&transfer_ix, ctx.accounts)?;
-
     // Enfoce wrapped meta to be uninitialized.
     let derivation_data: WrappedMetaDerivationData = (&*accs).into();
     accs.wrapped_meta
@@ -132,15 +127,34 @@ pub fn attest_token(
         payload.symbol = metadata.data.symbol;
     }
 
+    let payload = payload.try_to_vec()?;
+
+    // Pay fee
+    let bridge_fee = message_fee(
+        &accs.config.wormhole_bridge,
+        &accs.bridge.config,
+        accs.emitter.key,
+        ctx.accounts,
+        payload.len(),
+    )?;
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        bridge_fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
             nonce: data.nonce,
-            payload: payload.try_to_vec()?,
+            payload,
             consistency_level: ConsistencyLevel::Finalized,
         },
     );
 
+    // Bridges deployed before fee schedules don't take the fee schedule, so it is only passed on
+    // when the caller supplied it.
     let ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
@@ -154,7 +168,10 @@ pub fn attest_token(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
+        ]
+        .into_iter()
+        .chain(fee_schedule_meta(&accs.config.wormhole_bridge, ctx.accounts))
+        .collect(),
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
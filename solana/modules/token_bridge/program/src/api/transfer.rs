@@ -23,6 +23,10 @@ use crate::{
     },
 };
 use bridge::{
+    accounts::{
+        fee_schedule_meta,
+        message_fee,
+    },
     api::PostMessageData,
     types::ConsistencyLevel,
     vaa::SerializePayload,
@@ -124,8 +128,6 @@ pub fn transfer_native(
         &accs.custody,
         &accs.authority_signer,
         &accs.custody_signer,
-        &accs.bridge,
-        &accs.fee_collector,
         data.amount,
         data.fee,
     )?;
@@ -139,15 +141,34 @@ pub fn transfer_native(
         to_chain: data.target_chain,
         fee: U256::from(fee),
     };
+    let payload = payload.try_to_vec()?;
+
+    // Pay fee
+    let bridge_fee = message_fee(
+        &accs.config.wormhole_bridge,
+        &accs.bridge.config,
+        accs.emitter.key,
+        ctx.accounts,
+        payload.len(),
+    )?;
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        bridge_fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
             nonce: data.nonce,
-            payload: payload.try_to_vec()?,
+            payload,
             consistency_level: ConsistencyLevel::Finalized,
         },
     );
 
+    // Bridges deployed before fee schedules don't take the fee schedule, so it is only passed on
+    // when the caller supplied it.
     let ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
@@ -161,7 +182,10 @@ pub fn transfer_native(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
+        ]
+        .into_iter()
+        .chain(fee_schedule_meta(&accs.config.wormhole_bridge, ctx.accounts))
+        .collect(),
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
@@ -178,8 +202,6 @@ pub fn verify_and_execute_native_transfers(
     custody: &Mut<CustodyAccount<{ AccountState::MaybeInitialized }>>,
     authority_signer: &AuthoritySigner,
     custody_signer: &CustodySigner,
-    bridge: &Mut<CoreBridge<{ AccountState::Initialized }>>,
-    fee_collector: &Mut<Info>,
     raw_amount: u64,
     raw_fee: u64,
 ) -> Result<(u64, u64)> {
@@ -233,14 +255,6 @@ pub fn verify_and_execute_native_transfers(
     )?;
     invoke_seeded(&transfer_ix, ctx, authority_signer, None)?;
 
-    // Pay fee
-    let transfer_ix = solana_program::system_instruction::transfer(
-        payer.key,
-        fee_collector.key,
-        bridge.config.fee,
-    );
-    invoke(&transfer_ix, ctx.accounts)?;
-
     Ok((amount, fee))
 }
 
@@ -314,14 +328,11 @@ pub fn transfer_wrapped(
     verify_and_execute_wrapped_transfers(
         ctx,
         &derivation_data,
-        &accs.payer,
         &accs.from,
         &accs.from_owner,
         &accs.mint,
         &accs.wrapped_meta,
         &accs.authority_signer,
-        &accs.bridge,
-        &accs.fee_collector,
         data.amount,
         data.fee,
     )?;
@@ -335,15 +346,34 @@ pub fn transfer_wrapped(
         to_chain: data.target_chain,
         fee: U256::from(data.fee),
     };
+    let payload = payload.try_to_vec()?;
+
+    // Pay fee
+    let bridge_fee = message_fee(
+        &accs.config.wormhole_bridge,
+        &accs.bridge.config,
+        accs.emitter.key,
+        ctx.accounts,
+        payload.len(),
+    )?;
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        bridge_fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
             nonce: data.nonce,
-            payload: payload.try_to_vec()?,
+            payload,
             consistency_level: ConsistencyLevel::Finalized,
         },
     );
 
+    // Bridges deployed before fee schedules don't take the fee schedule, so it is only passed on
+    // when the caller supplied it.
     let ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
@@ -357,7 +387,10 @@ pub fn transfer_wrapped(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
+        ]
+        .into_iter()
+        .chain(fee_schedule_meta(&accs.config.wormhole_bridge, ctx.accounts))
+        .collect(),
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
@@ -368,14 +401,11 @@ pub fn transfer_wrapped(
 pub fn verify_and_execute_wrapped_transfers(
     ctx: &ExecutionContext,
     derivation_data: &WrappedMetaDerivationData,
-    payer: &Mut<Signer<AccountInfo>>,
     from: &Mut<Data<SplAccount, { AccountState::Initialized }>>,
     from_owner: &MaybeMut<Signer<Info>>,
     mint: &Mut<WrappedMint<{ AccountState::Initialized }>>,
     wrapped_meta: &WrappedTokenMeta<{ AccountState::Initialized }>,
     authority_signer: &AuthoritySigner,
-    bridge: &Mut<CoreBridge<{ AccountState::Initialized }>>,
-    fee_collector: &Mut<Info>,
     amount: u64,
     fee: u64,
 ) -> Result<()> {
@@ -408,14 +438,5 @@ pub fn verify_and_execute_wrapped_transfers(
     )?;
     invoke_seeded(&burn_ix, ctx, authority_signer, None)?;
 
-    // Pay fee
-    let transfer_ix = solana_program::system_instruction::transfer(
-        payer.key,
-        fee_collector.key,
-        bridge.config.fee,
-    );
-
-    invoke(&transfer_ix, ctx.accounts)?;
-
     Ok(())
 }
@@ -17,6 +17,10 @@ use crate::{
     TokenBridgeError::InvalidChain,
 };
 use bridge::{
+    accounts::{
+        fee_schedule_meta,
+        message_fee,
+    },
     api::PostMessageData,
     types::ConsistencyLevel,
     vaa::SerializePayload,
@@ -29,6 +33,7 @@ use solana_program::{
         AccountMeta,
         Instruction,
     },
+    program::invoke,
     pubkey::Pubkey,
     sysvar::clock::Clock,
 };
@@ -197,8 +202,6 @@ pub fn transfer_native_with_payload(
         &accs.custody,
         &accs.authority_signer,
         &accs.custody_signer,
-        &accs.bridge,
-        &accs.fee_collector,
         data.amount,
         0,
     )?;
@@ -213,15 +216,34 @@ pub fn transfer_native_with_payload(
         from_address: accs.sender.derive_sender_address(&data.cpi_program_id)?,
         payload: data.payload,
     };
+    let payload = payload.try_to_vec()?;
+
+    // Pay fee
+    let bridge_fee = message_fee(
+        &accs.config.wormhole_bridge,
+        &accs.bridge.config,
+        accs.emitter.key,
+        ctx.accounts,
+        payload.len(),
+    )?;
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        bridge_fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
             nonce: data.nonce,
-            payload: payload.try_to_vec()?,
+            payload,
             consistency_level: ConsistencyLevel::Finalized,
         },
     );
 
+    // Bridges deployed before fee schedules don't take the fee schedule, so it is only passed on
+    // when the caller supplied it.
     let ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
@@ -235,7 +257,10 @@ pub fn transfer_native_with_payload(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
+        ]
+        .into_iter()
+        .chain(fee_schedule_meta(&accs.config.wormhole_bridge, ctx.accounts))
+        .collect(),
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
@@ -320,14 +345,11 @@ pub fn transfer_wrapped_with_payload(
     verify_and_execute_wrapped_transfers(
         ctx,
         &derivation_data,
-        &accs.payer,
         &accs.from,
         &accs.from_owner,
         &accs.mint,
         &accs.wrapped_meta,
         &accs.authority_signer,
-        &accs.bridge,
-        &accs.fee_collector,
         data.amount,
         0,
     )?;
@@ -342,15 +364,34 @@ pub fn transfer_wrapped_with_payload(
         from_address: accs.sender.derive_sender_address(&data.cpi_program_id)?,
         payload: data.payload,
     };
+    let payload = payload.try_to_vec()?;
+
+    // Pay fee
+    let bridge_fee = message_fee(
+        &accs.config.wormhole_bridge,
+        &accs.bridge.config,
+        accs.emitter.key,
+        ctx.accounts,
+        payload.len(),
+    )?;
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        bridge_fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
     let params = (
         bridge::instruction::Instruction::PostMessage,
         PostMessageData {
             nonce: data.nonce,
-            payload: payload.try_to_vec()?,
+            payload,
             consistency_level: ConsistencyLevel::Finalized,
         },
     );
 
+    // Bridges deployed before fee schedules don't take the fee schedule, so it is only passed on
+    // when the caller supplied it.
     let ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
@@ -364,7 +405,10 @@ pub fn transfer_wrapped_with_payload(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
+        ]
+        .into_iter()
+        .chain(fee_schedule_meta(&accs.config.wormhole_bridge, ctx.accounts))
+        .collect(),
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
@@ -43,6 +43,7 @@ use crate::{
 use borsh::BorshSerialize;
 use bridge::{
     accounts::{
+        fee_schedule_key,
         Bridge,
         Claim,
         ClaimDerivationData,
@@ -468,6 +469,7 @@ fn claimable_vaa(
 /// | system_program   | system program                                                    | false  |
 /// | bridge_id        | bridge_id program                                                 | false  |
 /// | spl_token        | spl_token program                                                 | false  |
+/// | fee_schedule     | PDA(bridge_id,  \["FeeSchedule"\])                                | false  |
 pub fn transfer_native(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -521,6 +523,7 @@ pub fn transfer_native(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(fee_schedule_key(&bridge_id), false),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -548,6 +551,7 @@ pub fn transfer_native(
 /// | system_program   | system program                                                         | false  |
 /// | bridge_id        | bridge_id program                                                      | false  |
 /// | spl_token        | spl_token program                                                      | false  |
+/// | fee_schedule     | PDA(bridge_id,  \["FeeSchedule"\])                                     | false  |
 pub fn transfer_native_with_payload(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -607,6 +611,7 @@ pub fn transfer_native_with_payload(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(fee_schedule_key(&bridge_id), false),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -698,6 +703,7 @@ pub fn transfer_wrapped(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(fee_schedule_key(&bridge_id), false),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -796,6 +802,7 @@ pub fn transfer_wrapped_with_payload(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(fee_schedule_key(&bridge_id), false),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -854,6 +861,7 @@ pub fn attest(
             AccountMeta::new(solana_program::system_program::id(), false),
             // Program
             AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(fee_schedule_key(&bridge_id), false),
         ],
         data: (
             crate::instruction::Instruction::AttestToken,
@@ -386,6 +386,10 @@ mod tests {
     use primitive_types::U256;
     use rand::RngCore;
     use solana_program::pubkey::Pubkey;
+    use wormhole_conformance::{
+        Names,
+        TokenBridgePayload,
+    };
 
     #[test]
     pub fn test_serde_transfer() {
@@ -481,4 +485,52 @@ mod tests {
 
         assert_eq!(transfer_original, transfer_deser);
     }
+
+    /// Decode every case in the shared conformance corpus, the same corpus is decoded by the SDK
+    /// and by the token bridge contracts on other chains.
+    #[test]
+    pub fn test_conformance_corpus() {
+        let u256 = |v: U256| {
+            let mut out = [0u8; 32];
+            v.to_big_endian(&mut out);
+            out
+        };
+
+        wormhole_conformance::token_bridge(Names::Strict, |payload| match payload[0] {
+            1 => {
+                let t = PayloadTransfer::deserialize(&mut &payload[..]).unwrap();
+                TokenBridgePayload::Transfer {
+                    amount: u256(t.amount),
+                    token_address: t.token_address.to_vec(),
+                    token_chain: t.token_chain,
+                    to: t.to.to_vec(),
+                    to_chain: t.to_chain,
+                    fee: u256(t.fee),
+                }
+            }
+            3 => {
+                let t = PayloadTransferWithPayload::deserialize(&mut &payload[..]).unwrap();
+                TokenBridgePayload::TransferWithPayload {
+                    amount: u256(t.amount),
+                    token_address: t.token_address.to_vec(),
+                    token_chain: t.token_chain,
+                    to: t.to.to_vec(),
+                    to_chain: t.to_chain,
+                    from_address: t.from_address.to_vec(),
+                    payload: t.payload,
+                }
+            }
+            2 => {
+                let meta = PayloadAssetMeta::deserialize(&mut &payload[..]).unwrap();
+                TokenBridgePayload::AssetMeta {
+                    token_address: meta.token_address.to_vec(),
+                    token_chain: meta.token_chain,
+                    decimals: meta.decimals,
+                    symbol: meta.symbol,
+                    name: meta.name,
+                }
+            }
+            other => panic!("unexpected payload ID {}", other),
+        });
+    }
 }
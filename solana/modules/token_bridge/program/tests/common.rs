@@ -422,6 +422,58 @@ mod helpers {
         .await
     }
 
+    /// Post a core bridge governance VAA and apply it as the message fee schedule.
+    pub async fn set_message_fee_schedule(
+        client: &mut BanksClient,
+        bridge: Pubkey,
+        payer: &Keypair,
+        message_acc: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[bridge::instructions::set_message_fee_schedule(
+                bridge,
+                payer.pubkey(),
+                message_acc,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Post a core bridge governance VAA and apply it as an emitter's message fee override.
+    pub async fn set_emitter_message_fee(
+        client: &mut BanksClient,
+        bridge: Pubkey,
+        payer: &Keypair,
+        message_acc: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        fee_emitter: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[bridge::instructions::set_emitter_message_fee(
+                bridge,
+                payer.pubkey(),
+                message_acc,
+                emitter,
+                sequence,
+                fee_emitter,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn complete_native(
         client: &mut BanksClient,
         program: Pubkey,
@@ -1,9 +1,14 @@
 #![allow(dead_code)]
 use bridge::{
     accounts::{
+        FeeCollector,
         PostedVAA,
         PostedVAADerivationData,
     },
+    types::{
+        GovernancePayloadSetEmitterMessageFee,
+        GovernancePayloadSetMessageFeeSchedule,
+    },
     SerializePayload,
 };
 use libsecp256k1::SecretKey;
@@ -33,6 +38,7 @@ use std::{
 use token_bridge::{
     accounts::{
         ConfigAccount,
+        EmitterAccount,
         WrappedDerivationData,
         WrappedMint,
     },
@@ -357,6 +363,156 @@ async fn transfer_native() {
     .unwrap();
 }
 
+/// Post a core bridge governance VAA, returning the key of the posted VAA.
+async fn post_core_governance_vaa(
+    context: &mut Context,
+    message: Vec<u8>,
+    sequence: u64,
+) -> Pubkey {
+    let Context {
+        ref payer,
+        ref mut client,
+        ref bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let (vaa, body, _) =
+        common::generate_vaa(emitter.pubkey().to_bytes(), 1, message, nonce, sequence);
+    let signature_set = common::verify_signatures(client, bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, *bridge, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        bridge,
+    )
+}
+
+#[tokio::test]
+async fn transfer_native_pays_fee_schedule() {
+    let mut context = set_up().await.unwrap();
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap().pubkey();
+
+    // Charge a base fee of 100 plus 2 per byte of payload.
+    let message =
+        bridge::SerializeGovernancePayload::try_to_vec(&GovernancePayloadSetMessageFeeSchedule {
+            fee: U256::from(100u128),
+            fee_per_byte: U256::from(2u128),
+        })
+        .unwrap();
+    let message_key = post_core_governance_vaa(&mut context, message, 1).await;
+    common::set_message_fee_schedule(
+        &mut context.client,
+        context.bridge,
+        &context.payer,
+        message_key,
+        governance,
+        1,
+    )
+    .await
+    .unwrap();
+
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ..
+    } = context;
+
+    let fee_collector = FeeCollector::key(None, &bridge);
+    let payload_len = PayloadTransfer {
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: 1,
+        to: [0u8; 32],
+        to_chain: 2,
+        fee: U256::from(0u128),
+    }
+    .try_to_vec()
+    .unwrap()
+    .len() as u64;
+
+    let balance = common::get_account_balance(client, fee_collector).await;
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        &Keypair::new(),
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        balance + 100 + 2 * payload_len,
+    );
+
+    // Override the fee for the token bridge emitter, which then pays a flat fee of 10.
+    let message =
+        bridge::SerializeGovernancePayload::try_to_vec(&GovernancePayloadSetEmitterMessageFee {
+            emitter: EmitterAccount::key(None, &token_bridge),
+            fee: U256::from(10u128),
+            fee_per_byte: U256::from(0u128),
+        })
+        .unwrap();
+    let message_key = post_core_governance_vaa(&mut context, message, 2).await;
+    common::set_emitter_message_fee(
+        &mut context.client,
+        bridge,
+        &context.payer,
+        message_key,
+        governance,
+        2,
+        EmitterAccount::key(None, &token_bridge),
+    )
+    .await
+    .unwrap();
+
+    let Context {
+        ref payer,
+        ref mut client,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ..
+    } = context;
+
+    let balance = common::get_account_balance(client, fee_collector).await;
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        &Keypair::new(),
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        balance + 10,
+    );
+}
+
 async fn register_chain(context: &mut Context) {
     let Context {
         ref payer,
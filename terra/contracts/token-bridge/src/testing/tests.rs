@@ -1,10 +1,11 @@
 use cosmwasm_std::{Binary, StdResult};
 
-use wormhole::state::ParsedVAA;
+use wormhole::{byte_utils::get_string_from_32, state::ParsedVAA};
+use wormhole_conformance::{Names, TokenBridgePayload};
 
 use crate::{
     contract::{build_asset_id, build_native_id},
-    state::{Action, TokenBridgeMessage, TransferInfo, TransferWithPayloadInfo},
+    state::{Action, AssetMeta, TokenBridgeMessage, TransferInfo, TransferWithPayloadInfo},
 };
 
 #[test]
@@ -219,3 +220,57 @@ fn deserialize_transfer_with_payload_vaa() -> StdResult<()> {
 
     Ok(())
 }
+
+/// Decode every case in the shared conformance corpus, the same corpus is decoded by the SDK and by
+/// the token bridge contracts on other chains. Names are decoded the way the contract decodes them
+/// when attesting, which keeps invalid UTF-8 as replacement characters.
+#[test]
+fn conformance_corpus() {
+    wormhole_conformance::token_bridge(Names::Lossy, |payload| {
+        let message = TokenBridgeMessage::deserialize(&payload.to_vec()).unwrap();
+        match message.action {
+            Action::TRANSFER => {
+                let info = TransferInfo::deserialize(&message.payload).unwrap();
+                TokenBridgePayload::Transfer {
+                    amount: u256(info.amount),
+                    token_address: info.token_address.to_vec(),
+                    token_chain: info.token_chain,
+                    to: info.recipient.to_vec(),
+                    to_chain: info.recipient_chain,
+                    fee: u256(info.fee),
+                }
+            }
+            Action::TRANSFER_WITH_PAYLOAD => {
+                let info = TransferWithPayloadInfo::deserialize(&message.payload).unwrap();
+                TokenBridgePayload::TransferWithPayload {
+                    amount: u256(info.amount),
+                    token_address: info.token_address.to_vec(),
+                    token_chain: info.token_chain,
+                    to: info.recipient.to_vec(),
+                    to_chain: info.recipient_chain,
+                    from_address: info.sender_address.to_vec(),
+                    payload: info.payload,
+                }
+            }
+            Action::ATTEST_META => {
+                let meta = AssetMeta::deserialize(&message.payload).unwrap();
+                TokenBridgePayload::AssetMeta {
+                    token_address: meta.token_address.to_vec(),
+                    token_chain: meta.token_chain,
+                    decimals: meta.decimals,
+                    symbol: get_string_from_32(&meta.symbol),
+                    name: get_string_from_32(&meta.name),
+                }
+            }
+            other => panic!("unexpected action {}", other),
+        }
+    });
+}
+
+/// The contract keeps 256 bit amounts as their high and low 128 bits.
+fn u256((high, low): (u128, u128)) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[..16].copy_from_slice(&high.to_be_bytes());
+    out[16..].copy_from_slice(&low.to_be_bytes());
+    out
+}
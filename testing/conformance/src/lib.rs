@@ -0,0 +1,110 @@
+//! Runs the golden payloads in this directory against a decoder. Each implementation converts its
+//! own decoded types into the types below and the comparison against the expected fields is done
+//! here, so every decoder is held to exactly the same checks. See README.md for the corpus format.
+
+use serde_json::Value;
+
+/// A decoded token bridge payload, amounts are 32 byte big-endian and addresses are 32 bytes.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TokenBridgePayload {
+    Transfer {
+        amount: [u8; 32],
+        token_address: Vec<u8>,
+        token_chain: u16,
+        to: Vec<u8>,
+        to_chain: u16,
+        fee: [u8; 32],
+    },
+    TransferWithPayload {
+        amount: [u8; 32],
+        token_address: Vec<u8>,
+        token_chain: u16,
+        to: Vec<u8>,
+        to_chain: u16,
+        from_address: Vec<u8>,
+        payload: Vec<u8>,
+    },
+    AssetMeta {
+        token_address: Vec<u8>,
+        token_chain: u16,
+        decimals: u8,
+        symbol: String,
+        name: String,
+    },
+}
+
+/// How a decoder turns the 32 byte symbol and name of an asset into strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Names {
+    /// Null bytes and invalid UTF-8 are stripped, as done by the SDK and Solana.
+    Strict,
+    /// Null bytes are stripped and invalid UTF-8 is replaced, as done by Terra, CosmWasm and NEAR.
+    Lossy,
+}
+
+/// Decode every case in `token_bridge.json` with `decode` and compare the result to the expected
+/// fields, panicking with the name of the first case that differs.
+pub fn token_bridge(names: Names, decode: impl Fn(&[u8]) -> TokenBridgePayload) {
+    let corpus: Value = serde_json::from_str(include_str!("../token_bridge.json")).unwrap();
+
+    for case in corpus["cases"].as_array().unwrap() {
+        let name = case["name"].as_str().unwrap();
+        let payload = bytes(&case["payload"]);
+        assert_eq!(
+            decode(&payload),
+            expected_token_bridge(case["type"].as_str().unwrap(), &case["fields"], names),
+            "{}",
+            name
+        );
+    }
+}
+
+fn expected_token_bridge(kind: &str, f: &Value, names: Names) -> TokenBridgePayload {
+    match kind {
+        "transfer" => TokenBridgePayload::Transfer {
+            amount: u256(&f["amount"]),
+            token_address: bytes(&f["token_address"]),
+            token_chain: chain(&f["token_chain"]),
+            to: bytes(&f["to"]),
+            to_chain: chain(&f["to_chain"]),
+            fee: u256(&f["fee"]),
+        },
+        "transfer_with_payload" => TokenBridgePayload::TransferWithPayload {
+            amount: u256(&f["amount"]),
+            token_address: bytes(&f["token_address"]),
+            token_chain: chain(&f["token_chain"]),
+            to: bytes(&f["to"]),
+            to_chain: chain(&f["to_chain"]),
+            from_address: bytes(&f["from_address"]),
+            payload: bytes(&f["payload"]),
+        },
+        "asset_meta" => {
+            let (symbol, name) = match names {
+                Names::Strict => (&f["symbol"], &f["name"]),
+                Names::Lossy => (&f["symbol_lossy"], &f["name_lossy"]),
+            };
+            TokenBridgePayload::AssetMeta {
+                token_address: bytes(&f["token_address"]),
+                token_chain: chain(&f["token_chain"]),
+                decimals: f["decimals"].as_u64().unwrap() as u8,
+                symbol: symbol.as_str().unwrap().to_string(),
+                name: name.as_str().unwrap().to_string(),
+            }
+        }
+        other => panic!("unknown payload type {}", other),
+    }
+}
+
+fn bytes(v: &Value) -> Vec<u8> {
+    hex::decode(v.as_str().unwrap()).unwrap()
+}
+
+fn u256(v: &Value) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&bytes(v));
+    out
+}
+
+fn chain(v: &Value) -> u16 {
+    v.as_u64().unwrap() as u16
+}